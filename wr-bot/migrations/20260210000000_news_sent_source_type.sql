@@ -0,0 +1,14 @@
+-- Key sent-news dedup rows on (news_id, source_type) instead of a `stock_` id prefix
+ALTER TABLE forex_news_sent ADD COLUMN IF NOT EXISTS source_type TEXT NOT NULL DEFAULT 'forex';
+
+-- Swap the unique key before stripping the prefix, so a stock id that matches
+-- a forex id once stripped doesn't collide
+ALTER TABLE forex_news_sent DROP CONSTRAINT IF EXISTS forex_news_sent_news_id_key;
+ALTER TABLE forex_news_sent ADD CONSTRAINT forex_news_sent_news_id_source_type_key UNIQUE (news_id, source_type);
+
+UPDATE forex_news_sent
+SET source_type = 'stock', news_id = substr(news_id, 7)
+WHERE news_id LIKE 'stock\_%';
+
+DROP INDEX IF EXISTS idx_forex_news_id;
+CREATE INDEX IF NOT EXISTS idx_forex_news_sent_at ON forex_news_sent(source_type, sent_at);
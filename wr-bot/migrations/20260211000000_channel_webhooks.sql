@@ -0,0 +1,3 @@
+-- Optional webhook delivery for news channels
+ALTER TABLE forex_channels ADD COLUMN IF NOT EXISTS webhook_url TEXT;
+ALTER TABLE stock_news_channels ADD COLUMN IF NOT EXISTS webhook_url TEXT;
@@ -0,0 +1,6 @@
+-- Optional digest mode: buffer news per channel and post a summary periodically
+ALTER TABLE forex_channels ADD COLUMN IF NOT EXISTS digest BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE forex_channels ADD COLUMN IF NOT EXISTS digest_interval_minutes INTEGER NOT NULL DEFAULT 60;
+
+ALTER TABLE stock_news_channels ADD COLUMN IF NOT EXISTS digest BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE stock_news_channels ADD COLUMN IF NOT EXISTS digest_interval_minutes INTEGER NOT NULL DEFAULT 60;
@@ -0,0 +1,3 @@
+-- Optional role to ping for high impact news instead of @everyone
+ALTER TABLE forex_channels ADD COLUMN IF NOT EXISTS mention_role_id BIGINT;
+ALTER TABLE stock_news_channels ADD COLUMN IF NOT EXISTS mention_role_id BIGINT;
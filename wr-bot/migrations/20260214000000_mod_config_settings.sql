@@ -0,0 +1,4 @@
+-- Moderation settings managed by /modconfig
+ALTER TABLE mod_config ADD COLUMN IF NOT EXISTS warn_limit INTEGER;
+ALTER TABLE mod_config ADD COLUMN IF NOT EXISTS timeout_minutes INTEGER;
+ALTER TABLE mod_config ADD COLUMN IF NOT EXISTS mute_role_id BIGINT;
@@ -0,0 +1,11 @@
+-- Role-based mutes with their scheduled unmute time
+CREATE TABLE IF NOT EXISTS mutes (
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    role_id BIGINT NOT NULL,
+    unmute_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (guild_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_mutes_unmute_at ON mutes(unmute_at);
@@ -0,0 +1,2 @@
+-- Skip forex articles whose sentiment confidence is below this (0.0-1.0, NULL disables)
+ALTER TABLE forex_channels ADD COLUMN IF NOT EXISTS min_sentiment_confidence DOUBLE PRECISION;
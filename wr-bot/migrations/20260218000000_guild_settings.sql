@@ -0,0 +1,6 @@
+-- Per-guild presentation settings
+CREATE TABLE IF NOT EXISTS guild_settings (
+    guild_id BIGINT PRIMARY KEY,
+    locale VARCHAR(8) NOT NULL DEFAULT 'en',
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
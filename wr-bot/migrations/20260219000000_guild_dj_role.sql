@@ -0,0 +1,4 @@
+-- Role allowed to use disruptive music commands (NULL lets everyone use them)
+ALTER TABLE guild_settings ADD COLUMN IF NOT EXISTS dj_role_id BIGINT;
+-- Whether someone alone with the bot counts as a DJ
+ALTER TABLE guild_settings ADD COLUMN IF NOT EXISTS dj_alone_bypass BOOLEAN NOT NULL DEFAULT TRUE;
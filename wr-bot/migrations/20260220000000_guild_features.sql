@@ -0,0 +1,11 @@
+-- Per-guild switches for command categories
+CREATE TABLE IF NOT EXISTS guild_features (
+    guild_id BIGINT PRIMARY KEY,
+    forex BOOLEAN NOT NULL DEFAULT TRUE,
+    stock BOOLEAN NOT NULL DEFAULT TRUE,
+    calendar BOOLEAN NOT NULL DEFAULT TRUE,
+    music BOOLEAN NOT NULL DEFAULT TRUE,
+    ai BOOLEAN NOT NULL DEFAULT TRUE,
+    moderation BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
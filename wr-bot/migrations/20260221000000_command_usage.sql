@@ -0,0 +1,12 @@
+-- One row per finished command invocation
+CREATE TABLE IF NOT EXISTS command_usage (
+    id BIGSERIAL PRIMARY KEY,
+    command_name VARCHAR(100) NOT NULL,
+    guild_id BIGINT,
+    user_id BIGINT NOT NULL,
+    success BOOLEAN NOT NULL,
+    latency_ms INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_command_usage_created_at ON command_usage(created_at);
@@ -0,0 +1,10 @@
+-- Per-guild markers for stock news embeds; NULL keeps the default
+CREATE TABLE IF NOT EXISTS guild_embed_style (
+    guild_id BIGINT PRIMARY KEY,
+    impact_high TEXT,
+    impact_medium TEXT,
+    impact_low TEXT,
+    bullish TEXT,
+    bearish TEXT,
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
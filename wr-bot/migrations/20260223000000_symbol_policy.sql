@@ -0,0 +1,9 @@
+-- Per-guild symbol allow/deny entries. Any allow entry turns the list into
+-- an allowlist; deny entries always block.
+CREATE TABLE IF NOT EXISTS guild_symbol_policy (
+    guild_id BIGINT NOT NULL,
+    symbol VARCHAR(20) NOT NULL,
+    mode VARCHAR(8) NOT NULL CHECK (mode IN ('allow', 'deny')),
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (guild_id, symbol)
+);
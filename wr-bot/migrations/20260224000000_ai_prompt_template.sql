@@ -0,0 +1,2 @@
+-- Custom persona for chart analysis prompts (NULL uses the built-in prompt)
+ALTER TABLE guild_settings ADD COLUMN IF NOT EXISTS ai_analysis_prompt_template TEXT;
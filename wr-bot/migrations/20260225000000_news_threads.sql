@@ -0,0 +1,5 @@
+-- Optional delivery into a bot-managed thread under the news channel
+ALTER TABLE forex_channels ADD COLUMN IF NOT EXISTS use_thread BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE forex_channels ADD COLUMN IF NOT EXISTS thread_id BIGINT;
+ALTER TABLE stock_news_channels ADD COLUMN IF NOT EXISTS use_thread BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE stock_news_channels ADD COLUMN IF NOT EXISTS thread_id BIGINT;
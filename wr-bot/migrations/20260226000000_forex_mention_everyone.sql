@@ -0,0 +1,2 @@
+-- Per-channel @everyone override for high impact forex news; NULL follows the news server's flag
+ALTER TABLE forex_channels ADD COLUMN IF NOT EXISTS mention_everyone BOOLEAN;
@@ -0,0 +1,4 @@
+-- Per-guild embed branding; NULL keeps the bot's defaults
+ALTER TABLE guild_settings ADD COLUMN IF NOT EXISTS brand_footer TEXT;
+ALTER TABLE guild_settings ADD COLUMN IF NOT EXISTS brand_color INTEGER;
+ALTER TABLE guild_settings ADD COLUMN IF NOT EXISTS brand_thumbnail_url TEXT;
@@ -0,0 +1,49 @@
+use crate::repository::connection;
+use crate::services::{news_ws, stock_ws};
+use crate::utils::branding::branded_embed;
+use crate::utils::sys::{format_uptime, uptime};
+use poise::serenity_prelude as serenity;
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+type Context<'a> = poise::Context<'a, super::Data, Error>;
+
+fn status_label(connected: bool) -> &'static str {
+    if connected {
+        "Connected"
+    } else {
+        "Disconnected"
+    }
+}
+
+/// Show bot version, uptime and service status
+#[poise::command(slash_command, prefix_command)]
+pub async fn about(ctx: Context<'_>) -> Result<(), Error> {
+    let database = connection::ping(ctx.data().db.as_ref()).await.is_ok();
+    let guilds = ctx.cache().guild_count();
+
+    let services = format!(
+        "**News WS:** {}\n**Stock WS:** {}\n**Database:** {}\n**Music:** {}",
+        status_label(news_ws::is_connected()),
+        status_label(stock_ws::is_connected()),
+        status_label(database),
+        if ctx.data().music_player.is_some() {
+            "Enabled"
+        } else {
+            "Disabled"
+        },
+    );
+
+    let embed = serenity::CreateEmbed::default()
+        .title(format!("About {}", ctx.cache().current_user().name))
+        .field("Version", format!("`{}`", env!("CARGO_PKG_VERSION")), true)
+        .field("Uptime", format_uptime(uptime()), true)
+        .field("Servers", guilds.to_string(), true)
+        .field("Services", services, false)
+        .color(serenity::Colour::BLURPLE)
+        .timestamp(serenity::Timestamp::now());
+
+    let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
@@ -1,3 +1,14 @@
+use crate::commands::stock::clear_ticker_cache;
+use crate::repository::{CalendarRepository, ForexRepository, StockRepository, UsageRepository};
+use crate::services::news_ws::get_news_ws_service;
+use crate::services::stock_ws::get_stock_ws_client_async;
+use crate::services::ws_metrics::WsMetricsSnapshot;
+use crate::utils::branding;
+use crate::utils::features;
+use crate::utils::sys::{format_uptime, uptime};
+use poise::serenity_prelude as serenity;
+use sqlx::Connection;
+
 type Error = Box<dyn std::error::Error + Send + Sync>;
 type Context<'a> = poise::Context<'a, super::Data, Error>;
 
@@ -5,4 +16,280 @@ type Context<'a> = poise::Context<'a, super::Data, Error>;
 pub async fn everyone(ctx: Context<'_>) -> Result<(), Error> {
     ctx.say("@everyone").await?;
     Ok(())
-}
\ No newline at end of file
+}
+
+fn format_metrics(metrics: Option<WsMetricsSnapshot>) -> String {
+    let Some(m) = metrics else {
+        return "Not running".to_string();
+    };
+
+    let mut text = format!(
+        "Messages: `{}`\nReconnects: `{}`\nParse errors: `{}`\nOversized frames: `{}`",
+        m.messages_received, m.reconnects, m.parse_errors, m.oversized_frames
+    );
+
+    // Only the news WS deduplicates, so skip the section when nothing was counted
+    if m.delivered + m.duplicates_cached + m.duplicates_db > 0 {
+        text.push_str(&format!(
+            "\n**News**\nDelivered: `{}`\nDuplicates (memory): `{}`\nDuplicates (db): `{}`",
+            m.delivered, m.duplicates_cached, m.duplicates_db
+        ));
+    }
+
+    if !m.events.is_empty() {
+        text.push_str("\n**Events**");
+        for (event, count) in &m.events {
+            text.push_str(&format!("\n`{}`: {}", event, count));
+        }
+    }
+
+    text
+}
+
+/// Show WebSocket stream counters since startup
+#[poise::command(slash_command, prefix_command, owners_only)]
+pub async fn wsmetrics(ctx: Context<'_>) -> Result<(), Error> {
+    let news = get_news_ws_service().map(|s| s.metrics().snapshot());
+    let stock = get_stock_ws_client_async()
+        .await
+        .map(|c| c.metrics().snapshot());
+
+    let embed = serenity::CreateEmbed::default()
+        .title("WebSocket Metrics")
+        .description(format!(
+            "Cumulative since process start ({} ago)",
+            format_uptime(uptime())
+        ))
+        .field("News WS", format_metrics(news), true)
+        .field("Stock WS", format_metrics(stock), true)
+        .color(serenity::Colour::BLUE)
+        .timestamp(serenity::Timestamp::now());
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+/// Clear in-memory caches and re-read active channels from the database
+#[poise::command(slash_command, prefix_command, owners_only)]
+pub async fn reload(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let ticker_cache = if clear_ticker_cache() {
+        "Cleared"
+    } else {
+        "Was empty"
+    };
+    let dedup_cache = match get_news_ws_service() {
+        Some(service) => format!("Cleared {} entries", service.clear_dedup_cache()),
+        None => "News WS not running".to_string(),
+    };
+    let feature_cache = format!("Cleared {} guilds", features::clear_cache());
+    let branding_cache = format!("Cleared {} guilds", branding::clear_cache());
+
+    let pool = ctx.data().db.as_ref();
+    let forex = ForexRepository::get_active_channels(pool).await?.len();
+    let stock = StockRepository::get_active_channels(pool).await?.len();
+    let calendar = CalendarRepository::get_active_channels(pool).await?.len();
+
+    let embed = serenity::CreateEmbed::default()
+        .title("Reloaded")
+        .field("Ticker Autocomplete", ticker_cache, true)
+        .field("News Dedup Cache", dedup_cache, true)
+        .field("Feature Flags", feature_cache, true)
+        .field("Branding", branding_cache, true)
+        .field(
+            "Active Channels",
+            format!(
+                "Forex: `{}`\nStock: `{}`\nCalendar: `{}`",
+                forex, stock, calendar
+            ),
+            false,
+        )
+        .color(serenity::Colour::BLUE)
+        .timestamp(serenity::Timestamp::now());
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+const SQL_MAX_ROWS: i64 = 10;
+const SQL_OUTPUT_MAX: usize = 1800;
+/// Role `/sql` queries run as, created by `init-db/01-create-readonly-user.sh`.
+const SQL_ROLE: &str = "readonly";
+
+/// Run a read-only SELECT against the database
+#[poise::command(slash_command, prefix_command, owners_only)]
+pub async fn sql(
+    ctx: Context<'_>,
+    #[description = "A single SELECT statement"]
+    #[rest]
+    query: String,
+) -> Result<(), Error> {
+    let query = query.trim().trim_matches('`').trim();
+    let lowered = query.to_lowercase();
+
+    if !lowered.starts_with("select") || query.contains(';') {
+        ctx.send(
+            poise::CreateReply::default()
+                .content("Only a single `SELECT` statement without `;` is allowed.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    ctx.defer_ephemeral().await?;
+
+    // The query gets a connection of its own that is closed afterwards, so
+    // session state like advisory locks never goes back into the pool
+    let mut conn = ctx.data().db.acquire().await?.detach();
+    let mut tx = conn.begin().await?;
+    sqlx::query("SET TRANSACTION READ ONLY")
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("SET LOCAL statement_timeout = '5s'")
+        .execute(&mut *tx)
+        .await?;
+
+    // READ ONLY alone still lets side-effecting functions such as
+    // pg_terminate_backend run with the bot's privileges
+    if let Err(e) = sqlx::query(&format!("SET LOCAL ROLE {}", SQL_ROLE))
+        .execute(&mut *tx)
+        .await
+    {
+        eprintln!("[WARN] Failed to switch to the {} role: {}", SQL_ROLE, e);
+        ctx.send(
+            poise::CreateReply::default()
+                .content(format!(
+                    "The `{}` database role is missing or not granted to the bot \
+                     (see `init-db/01-create-readonly-user.sh`).",
+                    SQL_ROLE
+                ))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    // Rendering each row as JSON avoids decoding arbitrary column types
+    let rows = sqlx::query_scalar::<_, String>(&format!(
+        "SELECT row_to_json(q)::text FROM ({}) q LIMIT {}",
+        query,
+        SQL_MAX_ROWS + 1
+    ))
+    .fetch_all(&mut *tx)
+    .await;
+
+    let output = match rows {
+        Ok(rows) if rows.is_empty() => "(no rows)".to_string(),
+        Ok(rows) => {
+            let more = rows.len() as i64 > SQL_MAX_ROWS;
+            let mut output = rows
+                .iter()
+                .take(SQL_MAX_ROWS as usize)
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join("\n");
+            if output.chars().count() > SQL_OUTPUT_MAX {
+                output = output.chars().take(SQL_OUTPUT_MAX).collect::<String>() + "\n...";
+            }
+            if more {
+                output.push_str(&format!("\n(showing first {} rows)", SQL_MAX_ROWS));
+            }
+            output
+        }
+        Err(e) => format!("Error: {}", e),
+    };
+    let _ = tx.rollback().await;
+    let _ = conn.close().await;
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!("```json\n{}\n```", output.replace("```", "'''")))
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, Default, poise::ChoiceParameter)]
+pub enum StatsWindow {
+    #[name = "24 hours"]
+    Day,
+    #[default]
+    #[name = "7 days"]
+    Week,
+    #[name = "30 days"]
+    Month,
+}
+
+impl StatsWindow {
+    fn hours(self) -> i32 {
+        match self {
+            StatsWindow::Day => 24,
+            StatsWindow::Week => 24 * 7,
+            StatsWindow::Month => 24 * 30,
+        }
+    }
+}
+
+const STATS_TOP_COMMANDS: i64 = 15;
+
+/// Show the most used commands and their average latency
+#[poise::command(slash_command, prefix_command, owners_only)]
+pub async fn stats(
+    ctx: Context<'_>,
+    #[description = "Time window (default 7 days)"] window: Option<StatsWindow>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let window = window.unwrap_or_default();
+    let pool = ctx.data().db.as_ref();
+    let (total, avg_latency) = UsageRepository::totals(pool, window.hours()).await?;
+    let top = UsageRepository::top_commands(pool, window.hours(), STATS_TOP_COMMANDS).await?;
+
+    let list = if top.is_empty() {
+        "No commands recorded.".to_string()
+    } else {
+        top.iter()
+            .enumerate()
+            .map(|(i, s)| {
+                format!(
+                    "`{}.` **{}** - {} uses, {} failed, {:.0} ms avg",
+                    i + 1,
+                    s.command_name,
+                    s.uses,
+                    s.failures,
+                    s.avg_latency_ms
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let embed = serenity::CreateEmbed::default()
+        .title(format!(
+            "Command Usage ({})",
+            poise::ChoiceParameter::name(&window)
+        ))
+        .description(list)
+        .field("Total Invocations", format!("`{}`", total), true)
+        .field(
+            "Average Latency",
+            avg_latency
+                .map(|ms| format!("`{:.0} ms`", ms))
+                .unwrap_or_else(|| "`-`".to_string()),
+            true,
+        )
+        .color(serenity::Colour::BLUE)
+        .timestamp(serenity::Timestamp::now());
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
@@ -1,13 +1,69 @@
+use super::forex::symbol_available;
 use crate::config::Config;
 use crate::error::BotError;
+use crate::repository::GuildSettingsRepository;
 use crate::services::ai::Ai;
-use crate::services::gemini::GeminiService;
-use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use crate::services::gemini::{GeminiError, GeminiService};
+use crate::utils::branding;
+use crate::utils::embed;
+use crate::utils::ratelimit::RateLimiter;
+use crate::utils::template;
 use poise::CreateReply;
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::watch;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 type Context<'a> = poise::Context<'a, super::Data, Error>;
 
+/// How often a streamed answer is pushed to Discord while it is being generated.
+const STREAM_EDIT_INTERVAL: Duration = Duration::from_millis(750);
+/// Upper bound on progress edits per answer, to stay clear of edit rate limits.
+const MAX_EDITS: u32 = 20;
+const STREAM_PREVIEW_MAX: usize = 1900;
+
+static AI_LIMITER: OnceLock<Arc<RateLimiter>> = OnceLock::new();
+
+/// Per-user limiter shared by all AI commands, configured with
+/// `AI_RATE_LIMIT` requests per `AI_RATE_WINDOW_SECS` (default 5 per 60s).
+fn ai_limiter() -> &'static Arc<RateLimiter> {
+    AI_LIMITER.get_or_init(|| {
+        let requests = std::env::var("AI_RATE_LIMIT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(5);
+        let window = std::env::var("AI_RATE_WINDOW_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(60);
+
+        let limiter = Arc::new(RateLimiter::new(requests, Duration::from_secs(window)));
+        limiter.spawn_pruner(Duration::from_secs(300));
+        limiter
+    })
+}
+
+/// Command check that rejects users who exceeded the AI rate limit.
+async fn ai_rate_limit(ctx: Context<'_>) -> Result<bool, Error> {
+    match ai_limiter().check(ctx.author().id.get()) {
+        Ok(()) => Ok(true),
+        Err(wait) => {
+            let embed = embed::warning(
+                "Pelan-pelan",
+                &format!(
+                    "Terlalu banyak permintaan AI. Coba lagi dalam {} detik.",
+                    wait.as_secs().max(1)
+                ),
+            );
+            let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+            ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+                .await?;
+            Ok(false)
+        }
+    }
+}
+
 fn split_into_chunks(s: &str, max: usize) -> Vec<String> {
     let mut chunks = Vec::new();
     let mut start = 0;
@@ -26,6 +82,22 @@ fn split_into_chunks(s: &str, max: usize) -> Vec<String> {
     chunks
 }
 
+/// Friendly text for a failed Gemini call; quota errors get a retry hint.
+fn gemini_error_message(e: &Error, prefix: &str) -> String {
+    match e.downcast_ref::<GeminiError>() {
+        Some(GeminiError::RateLimited {
+            retry_after: Some(d),
+        }) => format!(
+            "AI sedang sibuk, coba lagi dalam {} detik.",
+            d.as_secs().max(1)
+        ),
+        Some(GeminiError::RateLimited { retry_after: None }) => {
+            "AI sedang sibuk, coba lagi sebentar lagi.".to_string()
+        }
+        None => format!("{}: {}", prefix, e),
+    }
+}
+
 async fn send_ai_response(ctx: Context<'_>, content: String) -> Result<(), Error> {
     const DISCORD_MAX_LEN: usize = 2000;
     const CHUNK_MAX: usize = 1900;
@@ -42,7 +114,13 @@ async fn send_ai_response(ctx: Context<'_>, content: String) -> Result<(), Error
     Ok(())
 }
 
-#[poise::command(prefix_command, slash_command, aliases("worm", "wr"))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    aliases("worm", "wr"),
+    check = "ai_rate_limit",
+    category = "AI"
+)]
 pub async fn worm(
     ctx: Context<'_>,
     #[rest]
@@ -95,7 +173,13 @@ pub async fn worm(
 
 
 ///Gemini AI
-#[poise::command(prefix_command, slash_command, aliases("gem", "gm"))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    aliases("gem", "gm"),
+    check = "ai_rate_limit",
+    category = "AI"
+)]
 pub async fn gemini(
     ctx: Context<'_>,
     #[rest]
@@ -119,12 +203,58 @@ pub async fn gemini(
 
     ctx.defer().await?;
 
-    match gemini.generate(&text).await {
+    let reply = ctx.say("Memproses...").await?;
+    let (partial_tx, mut partial_rx) = watch::channel(String::new());
+
+    let stream = gemini.generate_stream(&text, partial_tx);
+    tokio::pin!(stream);
+
+    let mut edit_interval = tokio::time::interval(STREAM_EDIT_INTERVAL);
+    let mut edits = 0;
+
+    let result = loop {
+        tokio::select! {
+            result = &mut stream => break result,
+            _ = edit_interval.tick() => {
+                if edits >= MAX_EDITS || !partial_rx.has_changed().unwrap_or(false) {
+                    continue;
+                }
+
+                let partial = partial_rx.borrow_and_update().clone();
+                let preview = match split_into_chunks(&partial, STREAM_PREVIEW_MAX).into_iter().next() {
+                    Some(first) if first.len() < partial.len() => format!("{}...", first),
+                    Some(first) => first,
+                    None => continue,
+                };
+
+                // A failed progress update shouldn't cost the final answer
+                if let Err(e) = reply.edit(ctx, CreateReply::default().content(preview)).await {
+                    eprintln!("[WARN] Failed to update streamed reply: {}", e);
+                }
+                edits += 1;
+            }
+        }
+    };
+
+    match result {
         Ok(response) => {
-            send_ai_response(ctx, response).await?;
+            let mut chunks = split_into_chunks(&response, 1900).into_iter();
+            if let Some(first) = chunks.next() {
+                reply
+                    .edit(ctx, CreateReply::default().content(first))
+                    .await?;
+            }
+            for chunk in chunks {
+                ctx.say(chunk).await?;
+            }
         }
         Err(e) => {
-            ctx.say(format!("Error: {}", e)).await?;
+            reply
+                .edit(
+                    ctx,
+                    CreateReply::default().content(gemini_error_message(&e, "Error")),
+                )
+                .await?;
         }
     }
 
@@ -132,7 +262,13 @@ pub async fn gemini(
 }
 
 /// Chat dengan Gemini dengan memory (ingat percakapan sebelumnya)
-#[poise::command(prefix_command, slash_command, aliases("gchat", "gc"))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    aliases("gchat", "gc"),
+    check = "ai_rate_limit",
+    category = "AI"
+)]
 pub async fn gemini_chat(
     ctx: Context<'_>,
     #[rest]
@@ -163,7 +299,7 @@ pub async fn gemini_chat(
             send_ai_response(ctx, response).await?;
         }
         Err(e) => {
-            ctx.say(format!("Error: {}", e)).await?;
+            ctx.say(gemini_error_message(&e, "Error")).await?;
         }
     }
 
@@ -171,7 +307,7 @@ pub async fn gemini_chat(
 }
 
 /// Hapus history chat Gemini
-#[poise::command(prefix_command, slash_command, aliases("gclear"))]
+#[poise::command(prefix_command, slash_command, aliases("gclear"), category = "AI")]
 pub async fn gemini_clear(ctx: Context<'_>) -> Result<(), Error> {
     let config = Config::from_env()
         .map_err(|e| BotError::Config(format!("Failed to load config: {}", e)))?;
@@ -195,7 +331,13 @@ pub async fn gemini_clear(ctx: Context<'_>) -> Result<(), Error> {
 }
 
 /// Analisis gambar dengan Gemini Vision
-#[poise::command(prefix_command, slash_command, aliases("gvision", "gv"))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    aliases("gvision", "gv"),
+    check = "ai_rate_limit",
+    category = "AI"
+)]
 pub async fn gemini_vision(
     ctx: Context<'_>,
     #[description = "URL gambar untuk dianalisis"]
@@ -233,11 +375,12 @@ pub async fn gemini_vision(
             if response.len() > 4000 {
                 send_ai_response(ctx, response).await?;
             } else {
+                let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
                 ctx.send(CreateReply::default().embed(embed)).await?;
             }
         }
         Err(e) => {
-            ctx.say(format!("Error: {}", e)).await?;
+            ctx.say(gemini_error_message(&e, "Error")).await?;
         }
     }
 
@@ -245,7 +388,12 @@ pub async fn gemini_vision(
 }
 
 /// Analisis chart trading dengan Gemini Vision (attach gambar langsung atau reply ke gambar)
-#[poise::command(prefix_command, aliases("market", "chart", "ta"))]
+#[poise::command(
+    prefix_command,
+    aliases("market", "chart", "ta"),
+    check = "ai_rate_limit",
+    category = "AI"
+)]
 pub async fn analisa(
     ctx: Context<'_>,
     #[description = "Symbol/Pair (contoh: BTCUSDT, EURUSD, XAUUSD)"]
@@ -256,6 +404,12 @@ pub async fn analisa(
     #[description = "Konteks tambahan (opsional)"]
     context: Option<String>,
 ) -> Result<(), Error> {
+    if let Some(symbol) = symbol.as_deref()
+        && !symbol_available(ctx, symbol).await?
+    {
+        return Ok(());
+    }
+
     let config = Config::from_env()
         .map_err(|e| BotError::Config(format!("Failed to load config: {}", e)))?;
 
@@ -301,11 +455,28 @@ pub async fn analisa(
         }
     };
 
-    let gemini = GeminiService::new(
-        config.gemini_api_key,
-        None,
-        config.gemini_prompt,
-    );
+    let persona = match ctx.guild_id() {
+        Some(guild_id) => {
+            GuildSettingsRepository::get_analysis_prompt(ctx.data().db.as_ref(), guild_id.get())
+                .await?
+        }
+        None => None,
+    };
+    let system_prompt = match persona.filter(|t| !t.trim().is_empty()) {
+        Some(template) => template::render(
+            &template,
+            &[
+                ("symbol", symbol.as_deref().unwrap_or("Unknown")),
+                ("timeframe", timeframe.as_deref().unwrap_or("Unknown")),
+                // User context goes in the user turn, never the system prompt;
+                // templates saved while `{context}` was allowed expand to nothing
+                ("context", ""),
+            ],
+        ),
+        None => config.gemini_prompt,
+    };
+
+    let gemini = GeminiService::new(config.gemini_api_key, None, system_prompt);
 
     let loading_msg = ctx.say("Menganalisis chart... Mohon tunggu sebentar.").await?;
 
@@ -335,20 +506,91 @@ pub async fn analisa(
                     .color(0x00C853)
                     .footer(CreateEmbedFooter::new("Bukan financial advice - DYOR"));
 
+                let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
                 ctx.send(CreateReply::default().embed(embed)).await?;
             }
         }
         Err(e) => {
             loading_msg.delete(ctx).await.ok();
-            ctx.say(format!("Error menganalisis chart: {}", e)).await?;
+            ctx.say(gemini_error_message(&e, "Error menganalisis chart"))
+                .await?;
         }
     }
 
     Ok(())
 }
 
+/// Placeholders available in `/aiprompt` templates.
+const PROMPT_PLACEHOLDERS: &[&str] = &["symbol", "timeframe"];
+
+/// Atur persona analisis chart untuk server ini (kosongkan untuk melihat)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    category = "AI"
+)]
+pub async fn aiprompt(
+    ctx: Context<'_>,
+    #[description = "Template persona; boleh memakai {symbol} dan {timeframe}"]
+    #[max_length = 1500]
+    template: Option<String>,
+    #[description = "Kembalikan ke prompt bawaan"] reset: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let pool = ctx.data().db.as_ref();
+
+    if reset.unwrap_or(false) {
+        GuildSettingsRepository::set_analysis_prompt(pool, guild_id, None).await?;
+        let embed = embed::success(
+            "Prompt Direset",
+            "Analisis chart kembali memakai prompt bawaan.",
+        );
+        let embed = branding::branded_embed(pool, ctx.guild_id(), embed).await;
+        ctx.send(CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let Some(template) = template else {
+        let current = GuildSettingsRepository::get_analysis_prompt(pool, guild_id).await?;
+        let description = match current.filter(|t| !t.trim().is_empty()) {
+            Some(t) => format!("```\n{}\n```", t),
+            None => "Memakai prompt bawaan.".to_string(),
+        };
+        let embed = embed::info("Prompt Analisis", &description);
+        let embed = branding::branded_embed(pool, ctx.guild_id(), embed).await;
+        ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    };
+
+    if let Err(reason) = template::validate(&template, PROMPT_PLACEHOLDERS) {
+        let embed = embed::error("Template Ditolak", &reason);
+        let embed = branding::branded_embed(pool, ctx.guild_id(), embed).await;
+        ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    GuildSettingsRepository::set_analysis_prompt(pool, guild_id, Some(template.trim())).await?;
+    let embed = embed::success(
+        "Prompt Disimpan",
+        "Analisis chart berikutnya akan memakai persona ini.",
+    );
+    let embed = branding::branded_embed(pool, ctx.guild_id(), embed).await;
+    ctx.send(CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
 /// Ringkas teks dengan Gemini
-#[poise::command(prefix_command, slash_command, aliases("gsum", "gs"))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    aliases("gsum", "gs"),
+    check = "ai_rate_limit",
+    category = "AI"
+)]
 pub async fn gemini_summarize(
     ctx: Context<'_>,
     #[rest]
@@ -383,11 +625,12 @@ pub async fn gemini_summarize(
             if response.len() > 4000 {
                 send_ai_response(ctx, response).await?;
             } else {
+                let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
                 ctx.send(CreateReply::default().embed(embed)).await?;
             }
         }
         Err(e) => {
-            ctx.say(format!("❌ Error: {}", e)).await?;
+            ctx.say(gemini_error_message(&e, "❌ Error")).await?;
         }
     }
 
@@ -395,7 +638,13 @@ pub async fn gemini_summarize(
 }
 
 /// Terjemahkan teks dengan Gemini
-#[poise::command(prefix_command, slash_command, aliases("gtrans", "gt"))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    aliases("gtrans", "gt"),
+    check = "ai_rate_limit",
+    category = "AI"
+)]
 pub async fn gemini_translate(
     ctx: Context<'_>,
     #[description = "Bahasa tujuan (contoh: Indonesia, English, Japanese)"]
@@ -433,11 +682,12 @@ pub async fn gemini_translate(
             if response.len() > 1000 || text.len() > 1000 {
                 ctx.say(format!("**🌐 Terjemahan ke {}:**\n\n{}", target_language, response)).await?;
             } else {
+                let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
                 ctx.send(CreateReply::default().embed(embed)).await?;
             }
         }
         Err(e) => {
-            ctx.say(format!("❌ Error: {}", e)).await?;
+            ctx.say(gemini_error_message(&e, "❌ Error")).await?;
         }
     }
 
@@ -445,7 +695,13 @@ pub async fn gemini_translate(
 }
 
 /// Generate code dengan Gemini
-#[poise::command(prefix_command, slash_command, aliases("gcode"))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    aliases("gcode"),
+    check = "ai_rate_limit",
+    category = "AI"
+)]
 pub async fn gemini_code(
     ctx: Context<'_>,
     #[description = "Bahasa pemrograman (contoh: Python, Rust, JavaScript)"]
@@ -476,7 +732,7 @@ pub async fn gemini_code(
             send_ai_response(ctx, format!("**💻 Code Generation ({}):**\n\n{}", language, response)).await?;
         }
         Err(e) => {
-            ctx.say(format!("❌ Error: {}", e)).await?;
+            ctx.say(gemini_error_message(&e, "❌ Error")).await?;
         }
     }
 
@@ -484,7 +740,13 @@ pub async fn gemini_code(
 }
 
 /// Jelaskan code dengan Gemini
-#[poise::command(prefix_command, slash_command, aliases("gexplain", "gexp"))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    aliases("gexplain", "gexp"),
+    check = "ai_rate_limit",
+    category = "AI"
+)]
 pub async fn gemini_explain(
     ctx: Context<'_>,
     #[rest]
@@ -513,10 +775,64 @@ pub async fn gemini_explain(
             send_ai_response(ctx, format!("**📖 Code Explanation:**\n\n{}", response)).await?;
         }
         Err(e) => {
-            ctx.say(format!("❌ Error: {}", e)).await?;
+            ctx.say(gemini_error_message(&e, "❌ Error")).await?;
         }
     }
 
     Ok(())
 }
 
+/// Tanya AI dan dapatkan jawaban dalam embed
+#[poise::command(
+    prefix_command,
+    slash_command,
+    check = "ai_rate_limit",
+    category = "AI"
+)]
+pub async fn aiask(
+    ctx: Context<'_>,
+    #[rest]
+    #[description = "Pertanyaan untuk AI"]
+    question: String,
+) -> Result<(), Error> {
+    let config = Config::from_env()
+        .map_err(|e| BotError::Config(format!("Failed to load config: {}", e)))?;
+
+    if config.gemini_api_key == "api_key" {
+        ctx.say("Fitur Gemini AI belum dikonfigurasi. Harap set `GEMINI_API_KEY` di environment.")
+            .await?;
+        return Ok(());
+    }
+
+    let gemini = GeminiService::new(config.gemini_api_key, None, config.prompt);
+
+    ctx.defer().await?;
+
+    match gemini.generate(&question).await {
+        Ok(answer) if answer.len() <= 4000 => {
+            let question_preview = split_into_chunks(&question, 1000)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let embed = CreateEmbed::new()
+                .title("Jawaban AI")
+                .description(answer)
+                .field("Pertanyaan", question_preview, false)
+                .color(0x4285F4)
+                .footer(CreateEmbedFooter::new(format!(
+                    "Ditanyakan oleh {}",
+                    ctx.author().name
+                )));
+            let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+            ctx.send(CreateReply::default().embed(embed)).await?;
+        }
+        Ok(answer) => {
+            send_ai_response(ctx, answer).await?;
+        }
+        Err(e) => {
+            ctx.say(gemini_error_message(&e, "Error")).await?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
 use crate::repository::CalendarRepository;
+use crate::utils::branding::branded_embed;
+use crate::utils::permissions::{can_post, cannot_post};
 use poise::serenity_prelude as serenity;
 use serenity::{CreateEmbed, CreateEmbedFooter, Timestamp};
 
@@ -9,7 +11,8 @@ type Context<'a> = poise::Context<'a, super::Data, Error>;
     slash_command,
     prefix_command,
     guild_only,
-    required_permissions = "ADMINISTRATOR"
+    required_permissions = "ADMINISTRATOR",
+    category = "Calendar"
 )]
 pub async fn calendar_setup(
     ctx: Context<'_>,
@@ -18,6 +21,13 @@ pub async fn calendar_setup(
     let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
     let channel_id = channel.id.get();
 
+    if !can_post(ctx, channel.id).await {
+        let embed = branded_embed(&ctx.data().db, ctx.guild_id(), cannot_post(channel.id)).await;
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
     let pool = ctx.data().db.as_ref();
     CalendarRepository::insert_channel(pool, guild_id, channel_id).await?;
 
@@ -37,6 +47,7 @@ pub async fn calendar_setup(
         .footer(CreateEmbedFooter::new("Fio Calendar"))
         .timestamp(Timestamp::now());
 
+    let embed = branded_embed(pool, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
@@ -45,7 +56,8 @@ pub async fn calendar_setup(
     slash_command,
     prefix_command,
     guild_only,
-    required_permissions = "ADMINISTRATOR"
+    required_permissions = "ADMINISTRATOR",
+    category = "Calendar"
 )]
 pub async fn calendar_disable(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
@@ -61,6 +73,7 @@ pub async fn calendar_disable(ctx: Context<'_>) -> Result<(), Error> {
         .color(serenity::Colour::from_rgb(158, 158, 158))
         .timestamp(Timestamp::now());
 
+    let embed = branded_embed(pool, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
@@ -69,7 +82,8 @@ pub async fn calendar_disable(ctx: Context<'_>) -> Result<(), Error> {
     slash_command,
     prefix_command,
     guild_only,
-    required_permissions = "ADMINISTRATOR"
+    required_permissions = "ADMINISTRATOR",
+    category = "Calendar"
 )]
 pub async fn calendar_enable(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
@@ -83,11 +97,12 @@ pub async fn calendar_enable(ctx: Context<'_>) -> Result<(), Error> {
         .color(serenity::Colour::from_rgb(220, 53, 69))
         .timestamp(Timestamp::now());
 
+    let embed = branded_embed(pool, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
 
-#[poise::command(slash_command, prefix_command, guild_only)]
+#[poise::command(slash_command, prefix_command, guild_only, category = "Calendar")]
 pub async fn calendar_status(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
 
@@ -122,6 +137,54 @@ pub async fn calendar_status(ctx: Context<'_>) -> Result<(), Error> {
             .timestamp(Timestamp::now()),
     };
 
+    let embed = branded_embed(pool, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+const HISTORY_DEFAULT: u32 = 10;
+const HISTORY_MAX: u32 = 25;
+
+/// Show calendar reminders the bot recently announced
+#[poise::command(slash_command, prefix_command, guild_only, category = "Calendar")]
+pub async fn calendar_history(
+    ctx: Context<'_>,
+    #[description = "Number of events (1-25, default 10)"]
+    #[min = 1]
+    #[max = 25]
+    limit: Option<u32>,
+) -> Result<(), Error> {
+    let limit = limit.unwrap_or(HISTORY_DEFAULT).clamp(1, HISTORY_MAX);
+
+    let pool = ctx.data().db.as_ref();
+    let events = CalendarRepository::recent_events(pool, limit as i64).await?;
+
+    // Discord renders <t:..> in each reader's own timezone
+    let description = if events.is_empty() {
+        "No reminders have been sent yet.".to_string()
+    } else {
+        events
+            .iter()
+            .map(|e| {
+                format!(
+                    "**{}**\n<t:{}:f> (<t:{}:R>)",
+                    e.event_title, e.sent_at, e.sent_at
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    };
+
+    let embed = CreateEmbed::default()
+        .title("Recent Calendar Reminders")
+        .description(description)
+        .footer(CreateEmbedFooter::new(
+            "History of announced reminders, not a forecast of upcoming events",
+        ))
+        .color(serenity::Colour::from_rgb(220, 53, 69))
+        .timestamp(Timestamp::now());
+
+    let embed = branded_embed(pool, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
@@ -130,7 +193,8 @@ pub async fn calendar_status(ctx: Context<'_>) -> Result<(), Error> {
     slash_command,
     prefix_command,
     guild_only,
-    required_permissions = "ADMINISTRATOR"
+    required_permissions = "ADMINISTRATOR",
+    category = "Calendar"
 )]
 pub async fn calendar_mention(
     ctx: Context<'_>,
@@ -151,6 +215,7 @@ pub async fn calendar_mention(
         .color(serenity::Colour::from_rgb(220, 53, 69))
         .timestamp(Timestamp::now());
 
+    let embed = branded_embed(pool, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
@@ -1,4 +1,16 @@
-use crate::repository::ForexRepository;
+use crate::handlers::features::require_feature;
+use crate::repository::{
+    CalendarRepository, ForexChannel, ForexRepository, NewsRepository, StockRepository,
+    SymbolPolicyRepository,
+};
+use crate::services::news_api::get_news_api;
+use crate::utils::branding;
+use crate::utils::features::FeatureCategory;
+use crate::utils::pairs::{normalize_symbol, pair_info};
+use crate::utils::permissions::{can_post, cannot_post};
+use crate::utils::sessions;
+use crate::utils::webhook::parse_webhook_url;
+use poise::ChoiceParameter;
 use poise::serenity_prelude as serenity;
 use serenity::{CreateEmbed, CreateEmbedFooter, Timestamp};
 
@@ -10,7 +22,8 @@ type Context<'a> = poise::Context<'a, super::Data, Error>;
     slash_command,
     prefix_command,
     guild_only,
-    required_permissions = "ADMINISTRATOR"
+    required_permissions = "ADMINISTRATOR",
+    category = "Forex"
 )]
 pub async fn forex_setup(
     ctx: Context<'_>,
@@ -19,6 +32,14 @@ pub async fn forex_setup(
     let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
     let channel_id = channel.id.get();
 
+    if !can_post(ctx, channel.id).await {
+        let embed =
+            branding::branded_embed(&ctx.data().db, ctx.guild_id(), cannot_post(channel.id)).await;
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
     let pool = ctx.data().db.as_ref();
     ForexRepository::insert_channel(pool, guild_id, channel_id).await?;
 
@@ -39,17 +60,314 @@ pub async fn forex_setup(
         .footer(CreateEmbedFooter::new("Updates every 60 seconds"))
         .timestamp(Timestamp::now());
 
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
 
-/// Disable forex news notifications
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum NewsKind {
+    #[name = "Forex news"]
+    Forex,
+    #[name = "Stock news"]
+    Stock,
+    #[name = "Economic calendar"]
+    Calendar,
+}
+
+impl NewsKind {
+    fn feature(self) -> FeatureCategory {
+        match self {
+            NewsKind::Forex => FeatureCategory::Forex,
+            NewsKind::Stock => FeatureCategory::Stock,
+            NewsKind::Calendar => FeatureCategory::Calendar,
+        }
+    }
+}
+
+// Serves several features, so the feature check runs per `kind` instead of
+// through a category.
+/// Set the channel for forex news, stock news or calendar reminders
 #[poise::command(
     slash_command,
     prefix_command,
     guild_only,
     required_permissions = "ADMINISTRATOR"
 )]
+pub async fn setnewschannel(
+    ctx: Context<'_>,
+    #[description = "Which feed to configure"] kind: NewsKind,
+    #[description = "Channel to post in"] channel: serenity::GuildChannel,
+    #[description = "Mention @everyone for high impact items"] mention_everyone: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    if !require_feature(ctx, guild_id, kind.feature()).await? {
+        return Ok(());
+    }
+    let channel_id = channel.id.get();
+    let mention = mention_everyone.unwrap_or(false);
+
+    if !can_post(ctx, channel.id).await {
+        let embed =
+            branding::branded_embed(&ctx.data().db, ctx.guild_id(), cannot_post(channel.id)).await;
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    let pool = ctx.data().db.as_ref();
+    let mention_note = match kind {
+        NewsKind::Forex => {
+            ForexRepository::subscribe(pool, guild_id, channel_id, Some(mention)).await?;
+            if mention { "Yes" } else { "No" }
+        }
+        NewsKind::Stock => {
+            StockRepository::subscribe(pool, guild_id, channel_id, mention).await?;
+            if mention { "Yes" } else { "No" }
+        }
+        NewsKind::Calendar => {
+            CalendarRepository::insert_channel(pool, guild_id, channel_id).await?;
+            CalendarRepository::set_mention_everyone(pool, guild_id, mention).await?;
+            if mention { "Yes" } else { "No" }
+        }
+    };
+
+    let embed = CreateEmbed::default()
+        .title("News Channel Set")
+        .description(format!(
+            "**{}** will be sent to <#{}>.",
+            kind.name(),
+            channel_id
+        ))
+        .field("Mention @everyone", mention_note, false)
+        .color(serenity::Colour::from_rgb(0, 150, 136))
+        .timestamp(Timestamp::now());
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Re-enable a feed previously configured for this channel
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn resubscribe(
+    ctx: Context<'_>,
+    #[description = "Which feed to re-enable"] kind: NewsKind,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    if !require_feature(ctx, guild_id, kind.feature()).await? {
+        return Ok(());
+    }
+    let channel_id = ctx.channel_id().get();
+
+    let pool = ctx.data().db.as_ref();
+    // Forex and calendar keep one row per guild, so it must point at this channel
+    let found = match kind {
+        NewsKind::Forex => {
+            let configured = ForexRepository::get_channel(pool, guild_id)
+                .await?
+                .is_some_and(|ch| ch.channel_id as u64 == channel_id);
+            if configured {
+                ForexRepository::enable_channel(pool, guild_id).await?;
+            }
+            configured
+        }
+        NewsKind::Stock => StockRepository::enable_channel(pool, channel_id).await?,
+        NewsKind::Calendar => {
+            let configured = CalendarRepository::get_channel(pool, guild_id)
+                .await?
+                .is_some_and(|ch| ch.channel_id as u64 == channel_id);
+            if configured {
+                CalendarRepository::enable_channel(pool, guild_id).await?;
+            }
+            configured
+        }
+    };
+
+    let embed = if found {
+        CreateEmbed::default()
+            .title("Resubscribed")
+            .description(format!(
+                "**{}** is active again in <#{}> with its previous settings.",
+                kind.name(),
+                channel_id
+            ))
+            .color(serenity::Colour::from_rgb(0, 150, 136))
+    } else {
+        CreateEmbed::default()
+            .title("Resubscribe")
+            .description(format!(
+                "No prior configuration found for **{}** in this channel. Use `/setnewschannel` instead.",
+                kind.name()
+            ))
+            .color(serenity::Colour::from_rgb(158, 158, 158))
+    };
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Most subscriptions listed per feed, to stay under the embed field limit.
+const SUBSCRIPTIONS_PER_FEED: usize = 8;
+
+fn active_label(is_active: bool) -> &'static str {
+    if is_active { "Active" } else { "Disabled" }
+}
+
+fn delivery_label(webhook: bool, digest: bool, digest_minutes: i32) -> String {
+    let mut parts = Vec::new();
+    if webhook {
+        parts.push("webhook".to_string());
+    }
+    if digest {
+        parts.push(format!("digest every {}m", digest_minutes));
+    }
+    if parts.is_empty() {
+        "instant".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+fn limit_lines(mut lines: Vec<String>) -> String {
+    if lines.is_empty() {
+        return "Not configured".to_string();
+    }
+    let extra = lines.len().saturating_sub(SUBSCRIPTIONS_PER_FEED);
+    lines.truncate(SUBSCRIPTIONS_PER_FEED);
+    if extra > 0 {
+        lines.push(format!("...and {} more", extra));
+    }
+    lines.join("\n")
+}
+
+// Left without a category on purpose: it is a read-only overview of every
+// feed and stays useful while some of them are disabled.
+/// List every news feed subscription in this server with its settings
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn subscriptions(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    let forex = ForexRepository::get_channels_for_guild(pool, guild_id).await?;
+    let stock = StockRepository::get_channels_for_guild(pool, guild_id).await?;
+    let calendar = CalendarRepository::get_channel(pool, guild_id).await?;
+
+    if forex.is_empty() && stock.is_empty() && calendar.is_none() {
+        let embed = CreateEmbed::default()
+            .title("News Subscriptions")
+            .description("This server has no news subscriptions. Use `/setnewschannel` to add one.")
+            .color(serenity::Colour::from_rgb(158, 158, 158));
+        let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    let forex_lines = forex
+        .iter()
+        .map(|ch| {
+            let mut line = format!(
+                "<#{}> - **{}** - {}",
+                ch.channel_id,
+                active_label(ch.is_active),
+                delivery_label(
+                    ch.webhook_url.is_some(),
+                    ch.digest,
+                    ch.digest_interval_minutes
+                )
+            );
+            if let Some(role_id) = ch.mention_role_id {
+                line.push_str(&format!("\nMentions <@&{}>", role_id));
+            }
+            if let Some(min) = ch.min_sentiment_confidence {
+                line.push_str(&format!("\nMin confidence {:.0}%", min * 100.0));
+            }
+            line
+        })
+        .collect();
+
+    let stock_lines = stock
+        .iter()
+        .map(|ch| {
+            let mut line = format!(
+                "<#{}> - **{}** - {}",
+                ch.channel_id,
+                active_label(ch.is_active),
+                delivery_label(
+                    ch.webhook_url.is_some(),
+                    ch.digest,
+                    ch.digest_interval_minutes
+                )
+            );
+            let filters: Vec<String> = [
+                ("tickers", ch.tickers_filter.as_deref()),
+                ("impact", ch.min_impact.as_deref()),
+                ("categories", ch.categories.as_deref()),
+            ]
+            .into_iter()
+            .filter_map(|(name, value)| {
+                value
+                    .filter(|v| !v.trim().is_empty())
+                    .map(|v| format!("{} `{}`", name, v))
+            })
+            .collect();
+            if !filters.is_empty() {
+                line.push_str(&format!("\nFilters: {}", filters.join(", ")));
+            }
+            match (ch.mention_role_id, ch.mention_everyone) {
+                (Some(role_id), _) => line.push_str(&format!("\nMentions <@&{}>", role_id)),
+                (None, true) => line.push_str("\nMentions @everyone"),
+                (None, false) => {}
+            }
+            line
+        })
+        .collect();
+
+    let calendar_lines = calendar
+        .iter()
+        .map(|ch| {
+            let mut line = format!("<#{}> - **{}**", ch.channel_id, active_label(ch.is_active));
+            if ch.mention_everyone {
+                line.push_str("\nMentions @everyone");
+            }
+            line
+        })
+        .collect();
+
+    let embed = CreateEmbed::default()
+        .title("News Subscriptions")
+        .field("Forex", limit_lines(forex_lines), false)
+        .field("Stock", limit_lines(stock_lines), false)
+        .field("Calendar", limit_lines(calendar_lines), false)
+        .color(serenity::Colour::from_rgb(0, 150, 136))
+        .timestamp(Timestamp::now());
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+/// Disable forex news notifications
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    category = "Forex"
+)]
 pub async fn forex_disable(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
 
@@ -64,6 +382,7 @@ pub async fn forex_disable(ctx: Context<'_>) -> Result<(), Error> {
         .color(serenity::Colour::from_rgb(158, 158, 158))
         .timestamp(Timestamp::now());
 
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
@@ -73,7 +392,8 @@ pub async fn forex_disable(ctx: Context<'_>) -> Result<(), Error> {
     slash_command,
     prefix_command,
     guild_only,
-    required_permissions = "ADMINISTRATOR"
+    required_permissions = "ADMINISTRATOR",
+    category = "Forex"
 )]
 pub async fn forex_enable(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
@@ -87,19 +407,218 @@ pub async fn forex_enable(ctx: Context<'_>) -> Result<(), Error> {
         .color(serenity::Colour::from_rgb(0, 150, 136))
         .timestamp(Timestamp::now());
 
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Deliver forex news through a webhook (leave empty to clear)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    category = "Forex"
+)]
+pub async fn forex_webhook(
+    ctx: Context<'_>,
+    #[description = "Discord webhook URL"] url: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    if let Some(url) = &url
+        && parse_webhook_url(url).is_none()
+    {
+        let embed = CreateEmbed::default()
+            .title("Invalid Webhook")
+            .description(
+                "The URL must be a Discord webhook (https://discord.com/api/webhooks/...).",
+            )
+            .color(serenity::Colour::from_rgb(220, 53, 69));
+        let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    let pool = ctx.data().db.as_ref();
+    let updated =
+        ForexRepository::set_webhook(pool, guild_id, url.as_deref().map(str::trim)).await?;
+
+    let embed = if !updated {
+        CreateEmbed::default()
+            .title("Forex News Webhook")
+            .description("Not configured. Use `/forex_setup` first.")
+            .color(serenity::Colour::from_rgb(158, 158, 158))
+    } else if url.is_some() {
+        CreateEmbed::default()
+            .title("Forex News Webhook")
+            .description("Forex news will be delivered through the webhook.")
+            .color(serenity::Colour::from_rgb(0, 150, 136))
+    } else {
+        CreateEmbed::default()
+            .title("Forex News Webhook")
+            .description("Webhook cleared. Forex news will be sent by the bot.")
+            .color(serenity::Colour::from_rgb(158, 158, 158))
+    };
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+/// Post forex news as a periodic digest instead of one message per article
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    category = "Forex"
+)]
+pub async fn forex_digest(
+    ctx: Context<'_>,
+    #[description = "Enable digest mode"] enabled: bool,
+    #[description = "Minutes between digests (5-1440, default 60)"] interval_minutes: Option<i32>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let interval = interval_minutes.unwrap_or(60).clamp(5, 1440);
+
+    let pool = ctx.data().db.as_ref();
+    let updated = ForexRepository::set_digest(pool, guild_id, enabled, interval).await?;
+
+    let embed = if !updated {
+        CreateEmbed::default()
+            .title("Forex News Digest")
+            .description("Not configured. Use `/forex_setup` first.")
+            .color(serenity::Colour::from_rgb(158, 158, 158))
+    } else if enabled {
+        CreateEmbed::default()
+            .title("Forex News Digest")
+            .description(format!(
+                "Forex news will be collected and posted as a digest every {} minutes.",
+                interval
+            ))
+            .color(serenity::Colour::from_rgb(0, 150, 136))
+    } else {
+        CreateEmbed::default()
+            .title("Forex News Digest")
+            .description("Digest disabled. Forex news will be posted as it arrives.")
+            .color(serenity::Colour::from_rgb(158, 158, 158))
+    };
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Ping a role instead of @everyone for high impact forex news
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    category = "Forex"
+)]
+pub async fn forex_mentionrole(
+    ctx: Context<'_>,
+    #[description = "Role to ping (leave empty to clear)"] role: Option<serenity::Role>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    let updated =
+        ForexRepository::set_mention_role(pool, guild_id, role.as_ref().map(|r| r.id.get()))
+            .await?;
+
+    let embed = if !updated {
+        CreateEmbed::default()
+            .title("Forex Mention Role")
+            .description("Not configured. Use `/forex_setup` first.")
+            .color(serenity::Colour::from_rgb(158, 158, 158))
+    } else if let Some(role) = role {
+        CreateEmbed::default()
+            .title("Forex Mention Role")
+            .description(format!("High impact news will ping <@&{}>.", role.id))
+            .color(serenity::Colour::from_rgb(0, 150, 136))
+    } else {
+        CreateEmbed::default()
+            .title("Forex Mention Role")
+            .description("Role cleared. High impact news will use @everyone when flagged.")
+            .color(serenity::Colour::from_rgb(158, 158, 158))
+    };
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Post forex news into a thread under the news channel
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    category = "Forex"
+)]
+pub async fn forex_usethread(
+    ctx: Context<'_>,
+    #[description = "Post news into a thread"] enabled: bool,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    let updated = ForexRepository::set_use_thread(pool, guild_id, enabled).await?;
+
+    let embed = if !updated {
+        CreateEmbed::default()
+            .title("Forex News Thread")
+            .description("Not configured. Use `/forex_setup` first.")
+            .color(serenity::Colour::from_rgb(158, 158, 158))
+    } else if enabled {
+        CreateEmbed::default()
+            .title("Forex News Thread")
+            .description(
+                "Forex news will be posted in a thread under the news channel, recreated if archived. \
+                 Without the Create Public Threads permission it falls back to the channel.",
+            )
+            .color(serenity::Colour::from_rgb(0, 150, 136))
+    } else {
+        CreateEmbed::default()
+            .title("Forex News Thread")
+            .description("Thread delivery disabled. Forex news will be posted in the channel.")
+            .color(serenity::Colour::from_rgb(158, 158, 158))
+    };
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
 
 /// Check forex news status
-#[poise::command(slash_command, prefix_command, guild_only)]
+#[poise::command(slash_command, prefix_command, guild_only, category = "Forex")]
 pub async fn forex_status(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
 
     let pool = ctx.data().db.as_ref();
     let channel = ForexRepository::get_channel(pool, guild_id).await?;
 
-    let embed = match channel {
+    let embed =
+        branding::branded_embed(&ctx.data().db, ctx.guild_id(), status_embed(channel)).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+fn mention_everyone_label(mention_everyone: Option<bool>) -> &'static str {
+    match mention_everyone {
+        Some(true) => "Yes (high impact)",
+        Some(false) => "No",
+        None => "Server default",
+    }
+}
+
+fn status_embed(channel: Option<ForexChannel>) -> CreateEmbed {
+    match channel {
         Some(ch) => {
             let status = if ch.is_active { "Active" } else { "Disabled" };
             let color = if ch.is_active {
@@ -108,26 +627,163 @@ pub async fn forex_status(ctx: Context<'_>) -> Result<(), Error> {
                 serenity::Colour::from_rgb(158, 158, 158)
             };
 
+            let min_confidence = ch
+                .min_sentiment_confidence
+                .map(|c| format!("{:.0}%", c * 100.0))
+                .unwrap_or_else(|| "Off".to_string());
+
+            let delivery = if ch.digest {
+                format!("Digest every {} min", ch.digest_interval_minutes)
+            } else if ch.use_thread {
+                "Thread".to_string()
+            } else {
+                "Channel".to_string()
+            };
+
             CreateEmbed::default()
                 .title("Forex News Status")
                 .field("Status", status, true)
                 .field("Channel", format!("<#{}>", ch.channel_id), true)
+                .field("Min Confidence", min_confidence, true)
+                .field(
+                    "Mention Everyone",
+                    mention_everyone_label(ch.mention_everyone),
+                    true,
+                )
+                .field("Delivery", delivery, true)
                 .color(color)
                 .timestamp(Timestamp::now())
         }
         None => CreateEmbed::default()
             .title("Forex News Status")
-            .description("Not configured. Use `/forex_setup` to enable.")
+            .description("Not configured. Use `/fnews subscribe` to enable.")
             .color(serenity::Colour::from_rgb(158, 158, 158))
             .timestamp(Timestamp::now()),
+    }
+}
+
+/// Forex news subscription commands
+#[poise::command(
+    slash_command,
+    subcommands("fnews_subscribe", "fnews_unsubscribe", "fnews_status"),
+    subcommand_required,
+    guild_only,
+    category = "Forex"
+)]
+pub async fn fnews(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Subscribe this channel to forex news alerts
+#[poise::command(
+    slash_command,
+    rename = "subscribe",
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    category = "Forex"
+)]
+pub async fn fnews_subscribe(
+    ctx: Context<'_>,
+    #[description = "Mention @everyone for high impact news (default: server setting)"]
+    mention_everyone: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let channel_id = ctx.channel_id();
+
+    if !can_post(ctx, channel_id).await {
+        let embed =
+            branding::branded_embed(&ctx.data().db, ctx.guild_id(), cannot_post(channel_id)).await;
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    let pool = ctx.data().db.as_ref();
+    let previous = ForexRepository::get_channel(pool, guild_id).await?;
+    ForexRepository::subscribe(pool, guild_id, channel_id.get(), mention_everyone).await?;
+
+    let mut embed = CreateEmbed::default()
+        .title("Forex News Subscribed")
+        .description(format!(
+            "Real-time forex news will be sent to <#{}>.",
+            channel_id
+        ))
+        .field("Sources", "FXStreet, Forex Factory, Investing.com", false)
+        .field(
+            "Mention Everyone",
+            mention_everyone_label(mention_everyone),
+            true,
+        )
+        .color(serenity::Colour::from_rgb(0, 150, 136))
+        .footer(CreateEmbedFooter::new("Use /fnews unsubscribe to stop"))
+        .timestamp(Timestamp::now());
+
+    if let Some(previous) = previous
+        && previous.is_active
+        && previous.channel_id as u64 != channel_id.get()
+    {
+        embed = embed.field(
+            "Moved",
+            format!("Forex news no longer goes to <#{}>.", previous.channel_id),
+            false,
+        );
+    }
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Stop forex news alerts for this server
+#[poise::command(
+    slash_command,
+    rename = "unsubscribe",
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    category = "Forex"
+)]
+pub async fn fnews_unsubscribe(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    let subscribed = ForexRepository::get_channel(pool, guild_id)
+        .await?
+        .is_some_and(|ch| ch.is_active);
+
+    let embed = if subscribed {
+        ForexRepository::disable_channel(pool, guild_id).await?;
+        CreateEmbed::default()
+            .title("Forex News Unsubscribed")
+            .description("Forex news alerts have been stopped for this server.")
+            .color(serenity::Colour::from_rgb(158, 158, 158))
+    } else {
+        CreateEmbed::default()
+            .title("Not Subscribed")
+            .description("This server is not subscribed to forex news.")
+            .color(serenity::Colour::from_rgb(158, 158, 158))
     };
 
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Check forex news subscription status
+#[poise::command(slash_command, rename = "status", guild_only, category = "Forex")]
+pub async fn fnews_status(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    let channel = ForexRepository::get_channel(pool, guild_id).await?;
+
+    let embed =
+        branding::branded_embed(&ctx.data().db, ctx.guild_id(), status_embed(channel)).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
 
 /// Get current high impact forex events
-#[poise::command(slash_command, prefix_command, aliases("calendar"))]
+#[poise::command(slash_command, prefix_command, aliases("calendar"), category = "Forex")]
 pub async fn forex_calendar(ctx: Context<'_>) -> Result<(), Error> {
     ctx.defer().await?;
 
@@ -204,6 +860,684 @@ pub async fn forex_calendar(ctx: Context<'_>) -> Result<(), Error> {
         .footer(CreateEmbedFooter::new("Source: Forex Factory"))
         .timestamp(Timestamp::now());
 
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum EconomicRange {
+    #[name = "Today"]
+    Today,
+    #[name = "Tomorrow"]
+    Tomorrow,
+    #[name = "This week"]
+    Week,
+}
+
+impl EconomicRange {
+    /// Window in UTC; days follow WIB like the calendar reminders.
+    fn bounds(self) -> (chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>) {
+        let wib = chrono::FixedOffset::east_opt(7 * 3600).unwrap();
+        let now = chrono::Utc::now();
+        let start_of_today = now
+            .with_timezone(&wib)
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_local_timezone(wib)
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+
+        match self {
+            EconomicRange::Today => (now, start_of_today + chrono::Duration::days(1)),
+            EconomicRange::Tomorrow => (
+                start_of_today + chrono::Duration::days(1),
+                start_of_today + chrono::Duration::days(2),
+            ),
+            EconomicRange::Week => (now, now + chrono::Duration::days(7)),
+        }
+    }
+}
+
+const MAX_ECONOMIC_EVENTS: usize = 25;
+
+/// List upcoming economic calendar events
+///
+/// Not registered in main.rs: the news server's calendar endpoint is still a
+/// stub that never returns events.
+#[poise::command(slash_command, prefix_command, category = "Forex")]
+pub async fn economic(
+    ctx: Context<'_>,
+    #[description = "Time range (default: today)"] range: Option<EconomicRange>,
+    #[description = "Filter by currency (e.g. USD, EUR)"] currency: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let range = range.unwrap_or(EconomicRange::Today);
+    let currency = currency
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty());
+    let (from, to) = range.bounds();
+
+    let result = match get_news_api() {
+        Some(api) => api.get_calendar(from, to, currency.as_deref()).await,
+        None => {
+            let embed = CreateEmbed::default()
+                .title("Economic Calendar")
+                .description("The news service is not configured.")
+                .color(serenity::Colour::from_rgb(158, 158, 158));
+            let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+            return Ok(());
+        }
+    };
+
+    let events = match result {
+        Ok(events) => events,
+        Err(e) => {
+            eprintln!("[WARN] Failed to fetch economic calendar: {}", e);
+            let embed = CreateEmbed::default()
+                .title("Economic Calendar")
+                .description(
+                    "The calendar service is unavailable right now. Please try again later.",
+                )
+                .color(serenity::Colour::from_rgb(220, 53, 69));
+            let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+            return Ok(());
+        }
+    };
+
+    // The service filters by currency too, but older deployments ignore it
+    let events: Vec<_> = events
+        .into_iter()
+        .filter(|e| {
+            currency
+                .as_deref()
+                .is_none_or(|c| e.currency.eq_ignore_ascii_case(c))
+        })
+        .collect();
+
+    let scope = match &currency {
+        Some(c) => format!("{} - {}", range.name(), c),
+        None => range.name().to_string(),
+    };
+
+    let description = if events.is_empty() {
+        "No events scheduled for this period.".to_string()
+    } else {
+        let mut description = String::new();
+        let mut current_time = None;
+
+        for event in events.iter().take(MAX_ECONOMIC_EVENTS) {
+            if current_time != Some(event.date_utc) {
+                current_time = Some(event.date_utc);
+                description.push_str(&format!("\n**<t:{}:f>**\n", event.date_utc.timestamp()));
+            }
+
+            let forecast = event
+                .forecast
+                .as_deref()
+                .filter(|f| !f.is_empty())
+                .unwrap_or("—");
+            let previous = event
+                .previous
+                .as_deref()
+                .filter(|p| !p.is_empty())
+                .unwrap_or("—");
+            description.push_str(&format!(
+                "`{}` {} [{}]\nForecast: `{}` | Previous: `{}`\n",
+                event.currency,
+                event.title,
+                event.impact.to_uppercase(),
+                forecast,
+                previous
+            ));
+        }
+
+        if events.len() > MAX_ECONOMIC_EVENTS {
+            description.push_str(&format!(
+                "\n+{} more events",
+                events.len() - MAX_ECONOMIC_EVENTS
+            ));
+        }
+        description
+    };
+
+    let embed = CreateEmbed::default()
+        .title(format!("Economic Calendar ({})", scope))
+        .description(description.chars().take(4000).collect::<String>())
+        .color(serenity::Colour::from_rgb(0, 150, 136))
+        .footer(CreateEmbedFooter::new(format!("{} events", events.len())))
+        .timestamp(Timestamp::now());
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Forex news commands
+#[poise::command(
+    slash_command,
+    subcommands("latest", "search", "minconfidence"),
+    subcommand_required,
+    category = "Forex"
+)]
+pub async fn news(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Search forex news by keyword
+#[poise::command(slash_command, category = "Forex")]
+pub async fn search(
+    ctx: Context<'_>,
+    #[description = "Keyword to search"] keyword: String,
+    #[description = "Number of results (max 10)"] limit: Option<i64>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let pool = ctx.data().db.as_ref();
+    let limit = limit.unwrap_or(5).clamp(1, 10);
+    let keyword = keyword.trim();
+
+    let news = ForexRepository::search_news(pool, keyword, limit).await?;
+
+    if news.is_empty() {
+        let embed = CreateEmbed::default()
+            .title("No Results")
+            .description(format!("No forex news found for \"{}\".", keyword))
+            .color(serenity::Colour::from_rgb(158, 158, 158));
+        let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let mut description = String::new();
+
+    for (i, item) in news.iter().enumerate() {
+        description.push_str(&format!(
+            "**{}. [{}]({})**\n{}\n\n",
+            i + 1,
+            item.title,
+            item.original_url,
+            item.summary
+                .as_deref()
+                .unwrap_or("")
+                .chars()
+                .take(150)
+                .collect::<String>()
+        ));
+    }
+
+    let embed = CreateEmbed::default()
+        .title(format!("Search Results: {}", keyword))
+        .description(description)
+        .color(serenity::Colour::from_rgb(0, 150, 136));
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Skip forex news whose sentiment confidence is below a threshold
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    category = "Forex"
+)]
+pub async fn minconfidence(
+    ctx: Context<'_>,
+    #[description = "Minimum confidence from 0.0 to 1.0 (leave empty to disable)"]
+    #[min = 0.0]
+    #[max = 1.0]
+    value: Option<f64>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    if let Some(v) = value
+        && !(0.0..=1.0).contains(&v)
+    {
+        let embed = CreateEmbed::default()
+            .title("Invalid Threshold")
+            .description("Confidence must be between 0.0 and 1.0.")
+            .color(serenity::Colour::from_rgb(158, 158, 158));
+        let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    let pool = ctx.data().db.as_ref();
+    let updated = ForexRepository::set_min_confidence(pool, guild_id, value).await?;
+
+    let embed = if !updated {
+        CreateEmbed::default()
+            .title("Forex Min Confidence")
+            .description("Not configured. Use `/forex_setup` first.")
+            .color(serenity::Colour::from_rgb(158, 158, 158))
+    } else if let Some(v) = value {
+        CreateEmbed::default()
+            .title("Forex Min Confidence")
+            .description(format!(
+                "News with sentiment confidence below {:.0}% will be skipped.",
+                v * 100.0
+            ))
+            .color(serenity::Colour::from_rgb(0, 150, 136))
+    } else {
+        CreateEmbed::default()
+            .title("Forex Min Confidence")
+            .description("Threshold disabled. All news will be sent.")
+            .color(serenity::Colour::from_rgb(158, 158, 158))
+    };
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Show the latest forex news
+#[poise::command(slash_command, category = "Forex")]
+pub async fn latest(
+    ctx: Context<'_>,
+    #[description = "Filter by currency (e.g. USD, EUR)"] currency: Option<String>,
+    #[description = "Number of news to show (max 10)"] limit: Option<i64>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let pool = ctx.data().db.as_ref();
+    let limit = limit.unwrap_or(5).clamp(1, 10);
+    let currency = currency.map(|c| c.trim().to_uppercase());
+
+    let news = ForexRepository::get_recent_news(pool, currency.as_deref(), limit).await?;
+
+    if news.is_empty() {
+        let description = match &currency {
+            Some(c) => format!("No forex news found for **{}**.", c),
+            None => "No forex news available yet.".to_string(),
+        };
+        let embed = CreateEmbed::default()
+            .title("No News")
+            .description(description)
+            .color(serenity::Colour::from_rgb(158, 158, 158));
+        let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let mut description = String::new();
+
+    for (i, item) in news.iter().enumerate() {
+        let sentiment_icon = match item.sentiment.as_deref() {
+            Some("bullish") => "+",
+            Some("bearish") => "-",
+            _ => " ",
+        };
+
+        let time_str = item
+            .published_at
+            .map(|t| t.format("%H:%M").to_string())
+            .unwrap_or_default();
+
+        description.push_str(&format!(
+            "**{}. [{}]({})** {}\n{} | {}\n\n",
+            i + 1,
+            item.title,
+            item.original_url,
+            sentiment_icon,
+            item.source_name.as_deref().unwrap_or("Unknown"),
+            time_str
+        ));
+    }
+
+    let title = match &currency {
+        Some(c) => format!("Latest Forex News - {}", c),
+        None => "Latest Forex News".to_string(),
+    };
+
+    let embed = CreateEmbed::default()
+        .title(title)
+        .description(description)
+        .color(serenity::Colour::from_rgb(0, 150, 136))
+        .footer(CreateEmbedFooter::new("Times in UTC"));
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+const TOP_NEWS_LIMIT: i64 = 15;
+
+/// Show today's high impact forex and stock headlines
+#[poise::command(slash_command, prefix_command, category = "Forex")]
+pub async fn topnews(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let pool = ctx.data().db.as_ref();
+    let news = NewsRepository::top_high_impact(pool, 24, TOP_NEWS_LIMIT).await?;
+
+    if news.is_empty() {
+        let embed = CreateEmbed::default()
+            .title("Top News")
+            .description("No high impact news in the last 24 hours.")
+            .color(serenity::Colour::from_rgb(158, 158, 158));
+        let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let mut description = String::new();
+
+    for (i, item) in news.iter().enumerate() {
+        let origin = if item.origin == "stock" {
+            "Stock"
+        } else {
+            "FX"
+        };
+        let time_str = item
+            .published_at
+            .map(|t| t.format("%d %b %H:%M").to_string())
+            .unwrap_or_else(|| "-".to_string());
+
+        description.push_str(&format!(
+            "**{}. `{}` [{}]({})**\n{} | {}\n\n",
+            i + 1,
+            origin,
+            item.title,
+            item.original_url,
+            item.source_name.as_deref().unwrap_or("Unknown"),
+            time_str
+        ));
+    }
+
+    let embed = CreateEmbed::default()
+        .title("Top News - Last 24h")
+        .description(description)
+        .color(serenity::Colour::from_rgb(0, 150, 136))
+        .footer(CreateEmbedFooter::new("High impact only | Times in UTC"));
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Show the 24h news sentiment for a currency
+#[poise::command(slash_command, prefix_command, category = "Forex")]
+pub async fn sentiment(
+    ctx: Context<'_>,
+    #[description = "Currency (e.g. USD, EUR)"] currency: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let pool = ctx.data().db.as_ref();
+    let currency = currency.trim().to_uppercase();
+
+    let stats = ForexRepository::get_sentiment_counts(pool, &currency, 24).await?;
+
+    let mut bullish = 0i64;
+    let mut bearish = 0i64;
+    let mut neutral = 0i64;
+
+    for (sentiment, count) in &stats {
+        match sentiment.as_deref() {
+            Some("bullish") => bullish += count,
+            Some("bearish") => bearish += count,
+            _ => neutral += count,
+        }
+    }
+
+    let total = bullish + bearish + neutral;
+
+    if total == 0 {
+        let embed = CreateEmbed::default()
+            .title(format!("{} Sentiment", currency))
+            .description(format!(
+                "No forex news mentioned **{}** in the last 24 hours.",
+                currency
+            ))
+            .color(serenity::Colour::from_rgb(158, 158, 158));
+        let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let bullish_pct = (bullish * 100) / total;
+    let bearish_pct = (bearish * 100) / total;
+    let net_score = ((bullish - bearish) * 100) / total;
+
+    let (dominant, color) = if bullish_pct > 60 {
+        ("Bullish", serenity::Colour::from_rgb(46, 204, 113))
+    } else if bearish_pct > 60 {
+        ("Bearish", serenity::Colour::from_rgb(231, 76, 60))
+    } else {
+        ("Neutral", serenity::Colour::from_rgb(158, 158, 158))
+    };
+
+    let embed = CreateEmbed::default()
+        .title(format!("{} Sentiment (24h)", currency))
+        .field("Dominant", dominant, true)
+        .field("Net Score", format!("{:+}", net_score), true)
+        .field("Articles", total.to_string(), true)
+        .field("Bullish", format!("{} ({}%)", bullish, bullish_pct), true)
+        .field("Bearish", format!("{} ({}%)", bearish, bearish_pct), true)
+        .field("Neutral", neutral.to_string(), true)
+        .color(color)
+        .footer(CreateEmbedFooter::new("Net score ranges from -100 to +100"))
+        .timestamp(Timestamp::now());
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Show pip size and quote precision for a currency pair
+#[poise::command(slash_command, prefix_command, category = "Forex")]
+pub async fn pairinfo(
+    ctx: Context<'_>,
+    #[description = "Pair symbol (e.g. EURUSD, XAUUSD)"] symbol: String,
+) -> Result<(), Error> {
+    if !symbol_available(ctx, &symbol).await? {
+        return Ok(());
+    }
+
+    let Some(info) = pair_info(&symbol) else {
+        let embed = CreateEmbed::default()
+            .title("Unknown Pair")
+            .description("Use a six-letter symbol such as `EURUSD` or `XAUUSD`.")
+            .color(serenity::Colour::from_rgb(158, 158, 158));
+        let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let mut embed = CreateEmbed::default()
+        .title(format!("{}/{}", info.base, info.quote))
+        .field("Category", info.category.label(), true)
+        .field("Quote Currency", &info.quote, true)
+        .field(
+            "Pip Size",
+            format!("{:.*}", info.pip_decimals as usize, info.pip_size()),
+            true,
+        )
+        .field(
+            "Price Precision",
+            format!("{} decimals", info.price_decimals),
+            true,
+        )
+        .field("Session", sessions::describe(chrono::Utc::now()), false)
+        .color(serenity::Colour::from_rgb(0, 150, 136))
+        .timestamp(Timestamp::now());
+
+    if !info.known {
+        embed = embed.footer(CreateEmbedFooter::new(
+            "Not in the pair table - precision is an assumption",
+        ));
+    }
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Checks the guild's symbol policy, replying when `symbol` is blocked.
+///
+/// Returns false when the command should stop. Outside guilds every symbol
+/// is available.
+pub(crate) async fn symbol_available(ctx: Context<'_>, symbol: &str) -> Result<bool, Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(true);
+    };
+
+    let pool = ctx.data().db.as_ref();
+    if SymbolPolicyRepository::is_allowed(pool, guild_id.get(), &normalize_symbol(symbol)).await? {
+        return Ok(true);
+    }
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content("That symbol isn't available on this server.")
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(false)
+}
+
+/// Limit which symbols can be queried in this server
+#[poise::command(
+    slash_command,
+    prefix_command,
+    subcommands("allow", "deny", "clear", "list"),
+    subcommand_required,
+    category = "Forex"
+)]
+pub async fn symbols(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+async fn set_symbol_policy(ctx: Context<'_>, symbol: &str, allow: bool) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let symbol = normalize_symbol(symbol);
+    if symbol.is_empty() || symbol.len() > 20 {
+        let embed = CreateEmbed::default()
+            .title("Invalid Symbol")
+            .description("Use a symbol such as `EURUSD` or `XAUUSD`.")
+            .color(serenity::Colour::from_rgb(158, 158, 158));
+        let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    let pool = ctx.data().db.as_ref();
+    SymbolPolicyRepository::set(pool, guild_id, &symbol, allow).await?;
+
+    let description = if allow {
+        format!(
+            "`{}` added to the allowlist. Only allowlisted symbols can be queried now.",
+            symbol
+        )
+    } else {
+        format!("`{}` can no longer be queried in this server.", symbol)
+    };
+    let embed = CreateEmbed::default()
+        .title("Symbol Policy Updated")
+        .description(description)
+        .color(serenity::Colour::from_rgb(0, 150, 136));
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Add a symbol to the allowlist (only allowlisted symbols can be used)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    category = "Forex"
+)]
+pub async fn allow(
+    ctx: Context<'_>,
+    #[description = "Symbol (e.g. EURUSD)"] symbol: String,
+) -> Result<(), Error> {
+    set_symbol_policy(ctx, &symbol, true).await
+}
+
+/// Block a symbol in this server
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    category = "Forex"
+)]
+pub async fn deny(
+    ctx: Context<'_>,
+    #[description = "Symbol (e.g. XAUUSD)"] symbol: String,
+) -> Result<(), Error> {
+    set_symbol_policy(ctx, &symbol, false).await
+}
+
+/// Remove every symbol rule so all symbols are available
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    category = "Forex"
+)]
+pub async fn clear(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    let removed = SymbolPolicyRepository::clear(pool, guild_id).await?;
+
+    let embed = CreateEmbed::default()
+        .title("Symbol Policy Cleared")
+        .description(format!(
+            "Removed {} rule(s). All symbols are available again.",
+            removed
+        ))
+        .color(serenity::Colour::from_rgb(0, 150, 136));
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Show the allowed and blocked symbols
+#[poise::command(slash_command, prefix_command, guild_only, category = "Forex")]
+pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    let entries = SymbolPolicyRepository::list(pool, guild_id).await?;
+
+    let join = |mode: &str| {
+        let symbols: Vec<String> = entries
+            .iter()
+            .filter(|e| e.mode == mode)
+            .map(|e| format!("`{}`", e.symbol))
+            .collect();
+        if symbols.is_empty() {
+            "None".to_string()
+        } else {
+            symbols.join(", ")
+        }
+    };
+
+    let embed = CreateEmbed::default()
+        .title("Symbol Policy")
+        .description(if entries.iter().any(|e| e.mode == "allow") {
+            "Only allowlisted symbols can be queried."
+        } else {
+            "All symbols can be queried except blocked ones."
+        })
+        .field("Allowed", join("allow"), false)
+        .field("Blocked", join("deny"), false)
+        .color(serenity::Colour::from_rgb(0, 150, 136));
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
@@ -1,3 +1,10 @@
+use super::moderation::{audit_log, user_label};
+use crate::repository::{GuildBranding, GuildSettingsRepository};
+use crate::utils::branding::{self, branded_embed, is_valid_thumbnail_url, parse_color};
+use crate::utils::embed;
+use crate::utils::features::{self, FeatureCategory};
+use crate::utils::locale::Locale;
+use crate::utils::text::EMBED_FOOTER_MAX;
 use chrono::{Duration, Utc};
 use poise::serenity_prelude::{self as serenity, GetMessages};
 
@@ -19,6 +26,213 @@ pub async fn say(
     Ok(())
 }
 
+/// Set how numbers are formatted in this server
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn setlocale(
+    ctx: Context<'_>,
+    #[description = "Number format"] locale: Locale,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    GuildSettingsRepository::set_locale(ctx.data().db.as_ref(), guild_id, locale.code()).await?;
+
+    let embed = embed::success(
+        "Locale Updated",
+        &format!(
+            "Numbers will be shown like `{}`.",
+            locale.format_number(1234.56, 2)
+        ),
+    );
+    let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Enable or disable a command category on this server
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn features(
+    ctx: Context<'_>,
+    #[description = "Command category"] category: FeatureCategory,
+    #[description = "Enable (true) or disable (false)"] enabled: bool,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    features::set(ctx.data().db.as_ref(), guild_id, category, enabled).await?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    let embed = embed::success(
+        "Feature Updated",
+        &format!(
+            "{} commands are now {} on this server.",
+            category.label(),
+            state
+        ),
+    );
+    let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Customize the footer, colour and thumbnail of the bot's embeds
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    subcommands(
+        "branding_footer",
+        "branding_color",
+        "branding_thumbnail",
+        "branding_show",
+        "branding_reset"
+    ),
+    subcommand_required
+)]
+pub async fn branding(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Loads the branding, lets `update` change it and saves the result.
+async fn update_branding(
+    ctx: Context<'_>,
+    update: impl FnOnce(&mut GuildBranding),
+) -> Result<GuildBranding, Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let pool = ctx.data().db.as_ref();
+
+    let mut current = branding::for_guild(pool, guild_id).await;
+    update(&mut current);
+    branding::set(pool, guild_id, &current).await?;
+    Ok(current)
+}
+
+async fn send_branding_preview(
+    ctx: Context<'_>,
+    title: &str,
+    branding: &GuildBranding,
+) -> Result<(), Error> {
+    let footer = branding.brand_footer.as_deref().unwrap_or("Default");
+    let color = branding
+        .brand_color
+        .map(|c| format!("#{:06X}", c))
+        .unwrap_or_else(|| "Default".to_string());
+    let thumbnail = branding.brand_thumbnail_url.as_deref().unwrap_or("None");
+
+    let embed = serenity::CreateEmbed::default()
+        .title(title)
+        .field("Footer", footer, false)
+        .field("Color", color, true)
+        .field("Thumbnail", thumbnail, true)
+        .color(embed::COLOR_INFO);
+
+    let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Set the embed footer text (leave empty to clear)
+#[poise::command(prefix_command, slash_command, guild_only, rename = "footer")]
+pub async fn branding_footer(
+    ctx: Context<'_>,
+    #[description = "Footer text"] text: Option<String>,
+) -> Result<(), Error> {
+    let text = text.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+
+    if let Some(text) = &text
+        && text.chars().count() > EMBED_FOOTER_MAX
+    {
+        let embed = embed::error(
+            "Footer Too Long",
+            &format!("The footer can be at most {} characters.", EMBED_FOOTER_MAX),
+        );
+        let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    let branding = update_branding(ctx, |b| b.brand_footer = text).await?;
+    send_branding_preview(ctx, "Branding Updated", &branding).await
+}
+
+/// Set the embed accent colour as hex, e.g. #1DB954 (leave empty to clear)
+#[poise::command(prefix_command, slash_command, guild_only, rename = "color")]
+pub async fn branding_color(
+    ctx: Context<'_>,
+    #[description = "Hex colour like #1DB954"] hex: Option<String>,
+) -> Result<(), Error> {
+    let color = match hex.as_deref() {
+        Some(hex) => match parse_color(hex) {
+            Some(color) => Some(color as i32),
+            None => {
+                let embed =
+                    embed::error("Invalid Color", "Use a six digit hex value like `#1DB954`.");
+                let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+                ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+                    .await?;
+                return Ok(());
+            }
+        },
+        None => None,
+    };
+
+    let branding = update_branding(ctx, |b| b.brand_color = color).await?;
+    send_branding_preview(ctx, "Branding Updated", &branding).await
+}
+
+/// Set the embed thumbnail image URL (leave empty to clear)
+#[poise::command(prefix_command, slash_command, guild_only, rename = "thumbnail")]
+pub async fn branding_thumbnail(
+    ctx: Context<'_>,
+    #[description = "Image URL (http or https)"] url: Option<String>,
+) -> Result<(), Error> {
+    if let Some(url) = &url
+        && !is_valid_thumbnail_url(url)
+    {
+        let embed = embed::error(
+            "Invalid URL",
+            "The thumbnail must be an http or https image URL.",
+        );
+        let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    let url = url.map(|u| u.trim().to_string());
+    let branding = update_branding(ctx, |b| b.brand_thumbnail_url = url).await?;
+    send_branding_preview(ctx, "Branding Updated", &branding).await
+}
+
+/// Show the current embed branding
+#[poise::command(prefix_command, slash_command, guild_only, rename = "show")]
+pub async fn branding_show(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let branding = branding::for_guild(ctx.data().db.as_ref(), guild_id).await;
+
+    send_branding_preview(ctx, "Branding", &branding).await
+}
+
+/// Restore the default embed branding
+#[poise::command(prefix_command, slash_command, guild_only, rename = "reset")]
+pub async fn branding_reset(ctx: Context<'_>) -> Result<(), Error> {
+    let branding = update_branding(ctx, |b| *b = GuildBranding::default()).await?;
+    send_branding_preview(ctx, "Branding Reset", &branding).await
+}
+
+const MAX_PURGE: u32 = 100;
+
+/// Bulk-delete recent messages, optionally filtered by author or text
 #[poise::command(
     prefix_command,
     slash_command,
@@ -27,22 +241,46 @@ pub async fn say(
 )]
 pub async fn purge(
     ctx: Context<'_>,
-    #[description = "Jumlah pesan yang akan dihapus (1-100)"]
+    #[description = "Jumlah pesan yang diperiksa (1-100)"]
     #[min = 1]
-    #[max = 100]
-    amount: u8,
+    count: u32,
+    #[description = "Hanya hapus pesan dari user ini"] user: Option<serenity::User>,
+    #[description = "Hanya hapus pesan yang mengandung teks ini"] contains: Option<String>,
 ) -> Result<(), Error> {
+    if count == 0 || count > MAX_PURGE {
+        let embed = embed::error(
+            "Jumlah Tidak Valid",
+            &format!("Jumlah pesan harus antara 1 dan {}.", MAX_PURGE),
+        );
+        let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
     let channel_id = ctx.channel_id();
     
     let messages = channel_id
-        .messages(&ctx.http(), GetMessages::new().limit(amount))
+        .messages(&ctx.http(), GetMessages::new().limit(count as u8))
         .await?;
 
+    let needle = contains.as_deref().map(str::to_lowercase);
+    let messages: Vec<_> = messages
+        .into_iter()
+        .filter(|m| user.as_ref().is_none_or(|u| m.author.id == u.id))
+        .filter(|m| {
+            needle
+                .as_deref()
+                .is_none_or(|n| m.content.to_lowercase().contains(n))
+        })
+        .collect();
+
     if messages.is_empty() {
         ctx.say("Tidak ada pesan untuk dihapus.").await?;
         return Ok(());
     }
 
+    // Discord rejects bulk deletes of messages older than 14 days
     let fourteen_days_ago = Utc::now() - Duration::days(14);
     let mut recent_messages: Vec<serenity::MessageId> = Vec::new();
     let mut old_messages: Vec<serenity::MessageId> = Vec::new();
@@ -56,7 +294,8 @@ pub async fn purge(
         }
     }
 
-    let total_count = recent_messages.len() + old_messages.len();
+    let mut deleted = recent_messages.len();
+    let mut failed = 0;
 
     if recent_messages.len() > 1 {
         channel_id
@@ -69,25 +308,60 @@ pub async fn purge(
     }
 
     for msg_id in old_messages {
-        if let Err(e) = channel_id.delete_message(&ctx.http(), msg_id).await {
-            eprintln!("Gagal menghapus pesan lama {}: {}", msg_id, e);
+        match channel_id.delete_message(&ctx.http(), msg_id).await {
+            Ok(()) => deleted += 1,
+            Err(e) => {
+                eprintln!("Gagal menghapus pesan lama {}: {}", msg_id, e);
+                failed += 1;
+            }
         }
         tokio::time::sleep(std::time::Duration::from_millis(300)).await;
     }
 
-    let embed_msg = ctx
-        .send(
-            poise::CreateReply::default().embed(
-                serenity::CreateEmbed::default()
-                    .title("Berhasil")
-                    .description(format!("Berhasil menghapus {} pesan.", total_count))
-                    .color(0x57F287),
-            ),
+    let mut description = format!("Berhasil menghapus {} pesan.", deleted);
+    if failed > 0 {
+        description.push_str(&format!(" {} pesan lama gagal dihapus.", failed));
+    }
+
+    let embed = serenity::CreateEmbed::default()
+        .title("Berhasil")
+        .description(&description)
+        .color(0x57F287);
+    let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    let embed_msg = ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    let mut filters = Vec::new();
+    if let Some(u) = &user {
+        filters.push(format!("author: {}", u.name));
+    }
+    if let Some(text) = &contains {
+        filters.push(format!("contains: \"{}\"", text));
+    }
+    let reason = if filters.is_empty() {
+        format!("Deleted {} of the last {} messages", deleted, count)
+    } else {
+        format!(
+            "Deleted {} of the last {} messages ({})",
+            deleted,
+            count,
+            filters.join(", ")
         )
-        .await?;
+    };
+
+    audit_log(
+        ctx,
+        embed::mod_action(
+            "Purge",
+            &format!("<#{}>", channel_id),
+            &user_label(ctx.author()),
+            &reason,
+            embed::COLOR_WARNING,
+        ),
+    )
+    .await;
 
     tokio::time::sleep(std::time::Duration::from_secs(3)).await;
     let _ = embed_msg.delete(ctx).await;
 
     Ok(())
-}
\ No newline at end of file
+}
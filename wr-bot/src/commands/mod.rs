@@ -1,3 +1,4 @@
+pub mod about;
 pub mod admin;
 pub mod ai;
 pub mod calendar;
@@ -1,35 +1,85 @@
-use crate::repository::ModerationRepository;
+use crate::repository::{ModerationRepository, MuteRepository};
+use crate::services::mutes;
+use crate::utils::branding;
 use crate::utils::embed;
 use poise::serenity_prelude as serenity;
-use serenity::{Colour, CreateEmbed, CreateEmbedFooter, Member, Mentionable, Timestamp};
+use serenity::{
+    ChannelType, Colour, CreateEmbed, CreateEmbedFooter, Member, Mentionable, Timestamp,
+};
 use std::time::Duration;
 
+/// Escalation timeout used when a warn limit is set without a duration
+const DEFAULT_TIMEOUT_MINUTES: i32 = 60;
+/// Discord caps member timeouts at 28 days
+const MAX_TIMEOUT_MINUTES: i32 = 28 * 24 * 60;
+/// Longest `/mute`, for both timeouts and the mute role, matching Discord's timeout cap
+const MAX_MUTE: Duration = Duration::from_secs(MAX_TIMEOUT_MINUTES as u64 * 60);
+
 type Error = Box<dyn std::error::Error + Send + Sync>;
 type Context<'a> = poise::Context<'a, super::Data, Error>;
 
 fn parse_duration(input: &str) -> Option<Duration> {
     let input = input.trim().to_lowercase();
-    let (num_str, unit) = input.split_at(input.len().saturating_sub(1));
-    let num: u64 = num_str.parse().ok()?;
-
-    match unit {
-        "s" => Some(Duration::from_secs(num)),
-        "m" => Some(Duration::from_secs(num * 60)),
-        "h" => Some(Duration::from_secs(num * 3600)),
-        "d" => Some(Duration::from_secs(num * 86400)),
-        _ => None,
+    // Split on the last char, not the last byte, so input like `5é` cannot panic
+    let unit = input.chars().last()?;
+    let num: u64 = input[..input.len() - unit.len_utf8()].parse().ok()?;
+
+    let secs = match unit {
+        's' => num,
+        'm' => num.checked_mul(60)?,
+        'h' => num.checked_mul(3600)?,
+        'd' => num.checked_mul(86400)?,
+        _ => return None,
+    };
+    Some(Duration::from_secs(secs))
+}
+
+/// Posts a moderation audit entry to the guild's log channel, if one is set.
+///
+/// Failures are only logged so they never undo or block the action itself.
+pub(crate) async fn audit_log(ctx: Context<'_>, embed: CreateEmbed) {
+    let Some(guild_id) = ctx.guild_id() else {
+        return;
+    };
+
+    let channel_id =
+        match ModerationRepository::log_channel(ctx.data().db.as_ref(), guild_id.get()).await {
+            Ok(Some(id)) => serenity::ChannelId::new(id),
+            Ok(None) => return,
+            Err(e) => {
+                eprintln!(
+                    "[WARN] Failed to load log channel for guild {}: {}",
+                    guild_id, e
+                );
+                return;
+            }
+        };
+
+    if let Err(e) = channel_id
+        .send_message(ctx.http(), serenity::CreateMessage::new().embed(embed))
+        .await
+    {
+        eprintln!(
+            "[WARN] Failed to post audit log to channel {}: {}",
+            channel_id, e
+        );
     }
 }
 
+pub(crate) fn user_label(user: &serenity::User) -> String {
+    format!("{} ({})", user.mention(), user.id)
+}
+
 #[poise::command(
     slash_command,
     prefix_command,
     guild_only,
-    required_permissions = "MODERATE_MEMBERS"
+    required_permissions = "MODERATE_MEMBERS",
+    category = "Moderation"
 )]
 pub async fn warn(
     ctx: Context<'_>,
-    #[description = "User to warn"] user: Member,
+    #[description = "User to warn"] mut user: Member,
     #[description = "Reason for warning"] reason: String,
 ) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
@@ -37,12 +87,14 @@ pub async fn warn(
 
     if user.user.id == moderator.id {
         let embed_err = embed::error("Cannot Warn", "You cannot warn yourself!");
+        let embed_err = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed_err).await;
         ctx.send(poise::CreateReply::default().embed(embed_err))
             .await?;
         return Ok(());
     }
     if user.user.bot {
         let embed_err = embed::error("Cannot Warn", "You cannot warn bots!");
+        let embed_err = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed_err).await;
         ctx.send(poise::CreateReply::default().embed(embed_err))
             .await?;
         return Ok(());
@@ -60,14 +112,42 @@ pub async fn warn(
     let warn_count =
         ModerationRepository::get_warning_count(pool, guild_id.get(), user.user.id.get()).await?;
 
+    let mut description = format!(
+        "**User:** {}\n**Reason:** {}\n**Total Warnings:** {}",
+        user.user.mention(),
+        reason,
+        warn_count
+    );
+
+    let mut escalation = None;
+    let config = ModerationRepository::get_config(pool, guild_id.get()).await?;
+    if let Some(config) = config
+        && let Some(limit) = config.warn_limit
+        && warn_count >= limit as i64
+    {
+        let minutes = config.timeout_minutes.unwrap_or(DEFAULT_TIMEOUT_MINUTES);
+        let until =
+            Timestamp::from_unix_timestamp(chrono::Utc::now().timestamp() + minutes as i64 * 60)?;
+
+        // The warning is already recorded, so report a failed timeout instead of erroring
+        match user
+            .disable_communication_until_datetime(&ctx.http(), until)
+            .await
+        {
+            Ok(()) => {
+                description.push_str(&format!(
+                    "\n**Escalation:** Timed out for {} minutes (limit: {})",
+                    minutes, limit
+                ));
+                escalation = Some(minutes);
+            }
+            Err(e) => description.push_str(&format!("\n**Escalation failed:** {}", e)),
+        }
+    }
+
     let embed = CreateEmbed::new()
         .title("User Warned")
-        .description(format!(
-            "**User:** {}\n**Reason:** {}\n**Total Warnings:** {}",
-            user.user.mention(),
-            reason,
-            warn_count
-        ))
+        .description(description)
         .color(Colour::ORANGE)
         .footer(CreateEmbedFooter::new(format!(
             "Warned by {}",
@@ -75,7 +155,37 @@ pub async fn warn(
         )))
         .timestamp(Timestamp::now());
 
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    audit_log(
+        ctx,
+        embed::mod_action(
+            "Warn",
+            &user_label(&user.user),
+            &user_label(moderator),
+            &reason,
+            embed::COLOR_WARNING,
+        ),
+    )
+    .await;
+
+    if let Some(minutes) = escalation {
+        audit_log(
+            ctx,
+            embed::mod_action(
+                "Timeout (warn limit)",
+                &user_label(&user.user),
+                &user_label(moderator),
+                &format!(
+                    "Reached {} warnings, timed out for {} minutes",
+                    warn_count, minutes
+                ),
+                embed::COLOR_ERROR,
+            ),
+        )
+        .await;
+    }
     Ok(())
 }
 
@@ -83,7 +193,8 @@ pub async fn warn(
     slash_command,
     prefix_command,
     guild_only,
-    required_permissions = "MODERATE_MEMBERS"
+    required_permissions = "MODERATE_MEMBERS",
+    category = "Moderation"
 )]
 pub async fn warnings(
     ctx: Context<'_>,
@@ -101,6 +212,7 @@ pub async fn warnings(
             .description(format!("{} has no warnings.", user.user.mention()))
             .color(Colour::DARK_GREEN)
             .timestamp(Timestamp::now());
+        let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
     }
@@ -130,6 +242,7 @@ pub async fn warnings(
         )))
         .timestamp(Timestamp::now());
 
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
@@ -138,7 +251,8 @@ pub async fn warnings(
     slash_command,
     prefix_command,
     guild_only,
-    required_permissions = "MODERATE_MEMBERS"
+    required_permissions = "MODERATE_MEMBERS",
+    category = "Moderation"
 )]
 pub async fn clearwarnings(
     ctx: Context<'_>,
@@ -164,39 +278,101 @@ pub async fn clearwarnings(
         )))
         .timestamp(Timestamp::now());
 
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    audit_log(
+        ctx,
+        embed::mod_action(
+            "Clear Warnings",
+            &user_label(&user.user),
+            &user_label(ctx.author()),
+            &format!("Cleared {} warning(s)", cleared),
+            embed::COLOR_INFO,
+        ),
+    )
+    .await;
     Ok(())
 }
 
-/// Timeout (mute) a user
+/// Mute a user with the configured mute role, or a timeout if none is set
 #[poise::command(
     slash_command,
     prefix_command,
     guild_only,
-    required_permissions = "MODERATE_MEMBERS"
+    required_permissions = "MODERATE_MEMBERS",
+    category = "Moderation"
 )]
 pub async fn mute(
     ctx: Context<'_>,
     #[description = "User to mute"] mut user: Member,
-    #[description = "Duration (e.g. 5m, 1h, 7d)"] duration: String,
+    #[description = "Duration (e.g. 5m, 1h, 7d), up to 28 days"] duration: String,
     #[description = "Reason"] reason: Option<String>,
 ) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
     let reason_text = reason.unwrap_or_else(|| "No reason provided".to_string());
 
     let dur = parse_duration(&duration).ok_or("Invalid duration format. Use: 5m, 1h, 7d")?;
-
-    if dur.as_secs() > 28 * 24 * 3600 {
-        let embed_err = embed::error("Invalid Duration", "Maximum timeout duration is 28 days.");
+    if dur > MAX_MUTE {
+        let embed_err = embed::error("Invalid Duration", "Maximum mute duration is 28 days.");
+        let embed_err = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed_err).await;
         ctx.send(poise::CreateReply::default().embed(embed_err))
             .await?;
         return Ok(());
     }
-    let timeout_until = serenity::Timestamp::from_unix_timestamp(
-        chrono::Utc::now().timestamp() + dur.as_secs() as i64,
-    )?;
+    let unmute_at = chrono::Duration::try_seconds(dur.as_secs() as i64)
+        .and_then(|dur| chrono::Utc::now().checked_add_signed(dur))
+        .ok_or("Invalid duration")?;
 
-    user.disable_communication_until_datetime(&ctx.http(), timeout_until)
+    let pool = ctx.data().db.as_ref();
+    let mute_role = ModerationRepository::get_config(pool, guild_id.get())
+        .await?
+        .and_then(|c| c.mute_role_id)
+        .map(|id| serenity::RoleId::new(id as u64));
+
+    let method = if let Some(role_id) = mute_role {
+        let cached = ctx.guild().map(|g| g.roles.contains_key(&role_id));
+        let role_exists = match cached {
+            Some(exists) => exists,
+            None => guild_id.roles(ctx.http()).await?.contains_key(&role_id),
+        };
+
+        if !role_exists {
+            let embed_err = embed::error(
+                "Mute Role Missing",
+                "The configured mute role no longer exists. Set a new one with `/modconfig set muterole`.",
+            );
+            let embed_err =
+                branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed_err).await;
+            ctx.send(poise::CreateReply::default().embed(embed_err))
+                .await?;
+            return Ok(());
+        }
+
+        user.add_role(ctx.http(), role_id).await?;
+        MuteRepository::schedule(
+            pool,
+            guild_id.get(),
+            user.user.id.get(),
+            role_id.get(),
+            unmute_at,
+        )
         .await?;
+        mutes::schedule_unmute(
+            ctx.serenity_context().http.clone(),
+            ctx.data().db.clone(),
+            guild_id.get(),
+            user.user.id.get(),
+            unmute_at,
+        );
+        "Mute"
+    } else {
+        let timeout_until = Timestamp::from_unix_timestamp(unmute_at.timestamp())?;
+
+        user.disable_communication_until_datetime(&ctx.http(), timeout_until)
+            .await?;
+        "Timeout"
+    };
 
     let embed = CreateEmbed::new()
         .title("User Muted")
@@ -213,21 +389,56 @@ pub async fn mute(
         )))
         .timestamp(Timestamp::now());
 
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    audit_log(
+        ctx,
+        embed::mod_action(
+            method,
+            &user_label(&user.user),
+            &user_label(ctx.author()),
+            &format!("{} (duration: {})", reason_text, duration),
+            embed::COLOR_ERROR,
+        ),
+    )
+    .await;
     Ok(())
 }
 
+/// Lift a mute role and/or timeout from a user
 #[poise::command(
     slash_command,
     prefix_command,
     guild_only,
-    required_permissions = "MODERATE_MEMBERS"
+    required_permissions = "MODERATE_MEMBERS",
+    category = "Moderation"
 )]
 pub async fn unmute(
     ctx: Context<'_>,
     #[description = "User to unmute"] mut user: Member,
 ) -> Result<(), Error> {
-    user.enable_communication(&ctx.http()).await?;
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+
+    let pool = ctx.data().db.as_ref();
+    let recorded_role = MuteRepository::cancel(pool, guild_id.get(), user.user.id.get()).await?;
+    // Members muted by hand only carry the configured role
+    let muted_role = match recorded_role {
+        Some(role_id) => Some(role_id),
+        None => ModerationRepository::get_config(pool, guild_id.get())
+            .await?
+            .and_then(|c| c.mute_role_id)
+            .map(|id| id as u64),
+    };
+
+    if let Some(role_id) = muted_role.map(serenity::RoleId::new)
+        && user.roles.contains(&role_id)
+    {
+        user.remove_role(ctx.http(), role_id).await?;
+    }
+    if user.communication_disabled_until.is_some() {
+        user.enable_communication(&ctx.http()).await?;
+    }
 
     let embed = CreateEmbed::new()
         .title("User Unmuted")
@@ -239,7 +450,20 @@ pub async fn unmute(
         )))
         .timestamp(Timestamp::now());
 
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    audit_log(
+        ctx,
+        embed::mod_action(
+            "Unmute",
+            &user_label(&user.user),
+            &user_label(ctx.author()),
+            "No reason provided",
+            embed::COLOR_SUCCESS,
+        ),
+    )
+    .await;
     Ok(())
 }
 
@@ -247,7 +471,8 @@ pub async fn unmute(
     slash_command,
     prefix_command,
     guild_only,
-    required_permissions = "KICK_MEMBERS"
+    required_permissions = "KICK_MEMBERS",
+    category = "Moderation"
 )]
 pub async fn kick(
     ctx: Context<'_>,
@@ -271,7 +496,20 @@ pub async fn kick(
         )))
         .timestamp(Timestamp::now());
 
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    audit_log(
+        ctx,
+        embed::mod_action(
+            "Kick",
+            &user_label(&user.user),
+            &user_label(ctx.author()),
+            &reason_text,
+            embed::COLOR_WARNING,
+        ),
+    )
+    .await;
     Ok(())
 }
 
@@ -279,7 +517,8 @@ pub async fn kick(
     slash_command,
     prefix_command,
     guild_only,
-    required_permissions = "BAN_MEMBERS"
+    required_permissions = "BAN_MEMBERS",
+    category = "Moderation"
 )]
 pub async fn ban(
     ctx: Context<'_>,
@@ -306,7 +545,20 @@ pub async fn ban(
         )))
         .timestamp(Timestamp::now());
 
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    audit_log(
+        ctx,
+        embed::mod_action(
+            "Ban",
+            &user_label(&user.user),
+            &user_label(ctx.author()),
+            &reason_text,
+            embed::COLOR_ERROR,
+        ),
+    )
+    .await;
     Ok(())
 }
 
@@ -314,7 +566,8 @@ pub async fn ban(
     slash_command,
     prefix_command,
     guild_only,
-    required_permissions = "BAN_MEMBERS"
+    required_permissions = "BAN_MEMBERS",
+    category = "Moderation"
 )]
 pub async fn unban(
     ctx: Context<'_>,
@@ -337,7 +590,20 @@ pub async fn unban(
         )))
         .timestamp(Timestamp::now());
 
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    audit_log(
+        ctx,
+        embed::mod_action(
+            "Unban",
+            &format!("<@{}> ({})", uid, uid),
+            &user_label(ctx.author()),
+            "No reason provided",
+            embed::COLOR_SUCCESS,
+        ),
+    )
+    .await;
     Ok(())
 }
 
@@ -345,7 +611,8 @@ pub async fn unban(
     slash_command,
     prefix_command,
     guild_only,
-    required_permissions = "ADMINISTRATOR"
+    required_permissions = "ADMINISTRATOR",
+    category = "Moderation"
 )]
 pub async fn autorole_set(
     ctx: Context<'_>,
@@ -365,6 +632,7 @@ pub async fn autorole_set(
         .color(Colour::DARK_GREEN)
         .timestamp(Timestamp::now());
 
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
@@ -373,7 +641,8 @@ pub async fn autorole_set(
     slash_command,
     prefix_command,
     guild_only,
-    required_permissions = "ADMINISTRATOR"
+    required_permissions = "ADMINISTRATOR",
+    category = "Moderation"
 )]
 pub async fn autorole_disable(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
@@ -387,6 +656,7 @@ pub async fn autorole_disable(ctx: Context<'_>) -> Result<(), Error> {
         .color(Colour::RED)
         .timestamp(Timestamp::now());
 
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
@@ -395,7 +665,8 @@ pub async fn autorole_disable(ctx: Context<'_>) -> Result<(), Error> {
     slash_command,
     prefix_command,
     guild_only,
-    required_permissions = "ADMINISTRATOR"
+    required_permissions = "ADMINISTRATOR",
+    category = "Moderation"
 )]
 pub async fn log_setup(
     ctx: Context<'_>,
@@ -409,12 +680,13 @@ pub async fn log_setup(
     let embed = CreateEmbed::new()
         .title("Logging Enabled")
         .description(format!(
-            "Member join/leave events will be logged to {}.",
+            "Member join/leave events and moderation actions will be logged to {}.",
             channel.mention()
         ))
         .color(Colour::DARK_GREEN)
         .timestamp(Timestamp::now());
 
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
@@ -423,7 +695,8 @@ pub async fn log_setup(
     slash_command,
     prefix_command,
     guild_only,
-    required_permissions = "ADMINISTRATOR"
+    required_permissions = "ADMINISTRATOR",
+    category = "Moderation"
 )]
 pub async fn log_disable(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
@@ -437,6 +710,268 @@ pub async fn log_disable(ctx: Context<'_>) -> Result<(), Error> {
         .color(Colour::RED)
         .timestamp(Timestamp::now());
 
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+fn or_not_set(value: Option<String>) -> String {
+    value.unwrap_or_else(|| "Not set".to_string())
+}
+
+/// View or change moderation settings for this server
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    subcommands("modconfig_show", "modconfig_set"),
+    subcommand_required,
+    category = "Moderation"
+)]
+pub async fn modconfig(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Show the current moderation settings
+#[poise::command(
+    slash_command,
+    rename = "show",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    category = "Moderation"
+)]
+pub async fn modconfig_show(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+
+    let pool = ctx.data().db.as_ref();
+    let config = ModerationRepository::get_config(pool, guild_id.get()).await?;
+
+    let (log_channel, warn_limit, timeout, mute_role, auto_role) = match config {
+        Some(c) => (
+            c.log_channel_id.map(|id| format!("<#{}>", id)),
+            c.warn_limit.map(|n| n.to_string()),
+            c.timeout_minutes.map(|m| format!("{} minutes", m)),
+            c.mute_role_id.map(|id| format!("<@&{}>", id)),
+            c.auto_role_id.map(|id| format!("<@&{}>", id)),
+        ),
+        None => (None, None, None, None, None),
+    };
+
+    let embed = CreateEmbed::new()
+        .title("Moderation Config")
+        .field("Log Channel", or_not_set(log_channel), true)
+        .field("Warn Limit", or_not_set(warn_limit), true)
+        .field(
+            "Timeout Duration",
+            timeout.unwrap_or_else(|| format!("{} minutes (default)", DEFAULT_TIMEOUT_MINUTES)),
+            true,
+        )
+        .field("Mute Role", or_not_set(mute_role), true)
+        .field("Auto Role", or_not_set(auto_role), true)
+        .color(Colour::BLURPLE)
+        .timestamp(Timestamp::now());
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
+
+/// Change a moderation setting
+#[poise::command(
+    slash_command,
+    rename = "set",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    subcommands(
+        "modconfig_set_log_channel",
+        "modconfig_set_warn_limit",
+        "modconfig_set_timeout",
+        "modconfig_set_mute_role"
+    ),
+    subcommand_required,
+    category = "Moderation"
+)]
+pub async fn modconfig_set(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+async fn send_config_updated(ctx: Context<'_>, setting: &str, value: String) -> Result<(), Error> {
+    let embed = CreateEmbed::new()
+        .title("Moderation Config Updated")
+        .field(setting, value, false)
+        .color(Colour::DARK_GREEN)
+        .timestamp(Timestamp::now());
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Set the moderation log channel (leave empty to disable)
+#[poise::command(
+    slash_command,
+    rename = "logchannel",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    category = "Moderation"
+)]
+pub async fn modconfig_set_log_channel(
+    ctx: Context<'_>,
+    #[description = "Text channel for logs"] channel: Option<serenity::GuildChannel>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+    let pool = ctx.data().db.as_ref();
+
+    let Some(channel) = channel else {
+        ModerationRepository::disable_logging(pool, guild_id.get()).await?;
+        return send_config_updated(ctx, "Log Channel", "Not set".to_string()).await;
+    };
+
+    if channel.guild_id != guild_id
+        || !matches!(channel.kind, ChannelType::Text | ChannelType::News)
+    {
+        let embed_err = embed::error(
+            "Invalid Channel",
+            "The log channel must be a text channel in this server.",
+        );
+        let embed_err = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed_err).await;
+        ctx.send(poise::CreateReply::default().embed(embed_err))
+            .await?;
+        return Ok(());
+    }
+
+    ModerationRepository::set_log_channel(pool, guild_id.get(), channel.id.get()).await?;
+    send_config_updated(ctx, "Log Channel", channel.mention().to_string()).await
+}
+
+/// Set how many warnings trigger an automatic timeout (leave empty to disable)
+#[poise::command(
+    slash_command,
+    rename = "warnlimit",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    category = "Moderation"
+)]
+pub async fn modconfig_set_warn_limit(
+    ctx: Context<'_>,
+    #[description = "Number of warnings (1-100)"]
+    #[min = 1]
+    #[max = 100]
+    limit: Option<i32>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+    let limit = limit.map(|n| n.clamp(1, 100));
+
+    let pool = ctx.data().db.as_ref();
+    ModerationRepository::set_warn_limit(pool, guild_id.get(), limit).await?;
+
+    send_config_updated(ctx, "Warn Limit", or_not_set(limit.map(|n| n.to_string()))).await
+}
+
+/// Set the timeout length used when the warn limit is reached
+#[poise::command(
+    slash_command,
+    rename = "timeout",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    category = "Moderation"
+)]
+pub async fn modconfig_set_timeout(
+    ctx: Context<'_>,
+    #[description = "Duration (e.g. 30m, 2h, 1d); leave empty for the default"] duration: Option<
+        String,
+    >,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+
+    let minutes = match duration {
+        Some(d) => {
+            let dur = parse_duration(&d).ok_or("Invalid duration format. Use: 30m, 2h, 1d")?;
+            let minutes = i32::try_from(dur.as_secs() / 60).unwrap_or(i32::MAX);
+            if !(1..=MAX_TIMEOUT_MINUTES).contains(&minutes) {
+                let embed_err = embed::error(
+                    "Invalid Duration",
+                    "Timeout must be between 1 minute and 28 days.",
+                );
+                let embed_err =
+                    branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed_err).await;
+                ctx.send(poise::CreateReply::default().embed(embed_err))
+                    .await?;
+                return Ok(());
+            }
+            Some(minutes)
+        }
+        None => None,
+    };
+
+    let pool = ctx.data().db.as_ref();
+    ModerationRepository::set_timeout_minutes(pool, guild_id.get(), minutes).await?;
+
+    let value = match minutes {
+        Some(m) => format!("{} minutes", m),
+        None => format!("{} minutes (default)", DEFAULT_TIMEOUT_MINUTES),
+    };
+    send_config_updated(ctx, "Timeout Duration", value).await
+}
+
+/// Set the role used to mute members (leave empty to clear)
+#[poise::command(
+    slash_command,
+    rename = "muterole",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    category = "Moderation"
+)]
+pub async fn modconfig_set_mute_role(
+    ctx: Context<'_>,
+    #[description = "Mute role"] role: Option<serenity::Role>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+    let pool = ctx.data().db.as_ref();
+
+    let Some(role) = role else {
+        ModerationRepository::set_mute_role(pool, guild_id.get(), None).await?;
+        return send_config_updated(ctx, "Mute Role", "Not set".to_string()).await;
+    };
+
+    // @everyone shares the guild's id and cannot be assigned
+    if role.guild_id != guild_id || role.id.get() == guild_id.get() || role.managed {
+        let embed_err = embed::error(
+            "Invalid Role",
+            "Pick a regular role from this server that the bot can assign.",
+        );
+        let embed_err = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed_err).await;
+        ctx.send(poise::CreateReply::default().embed(embed_err))
+            .await?;
+        return Ok(());
+    }
+
+    ModerationRepository::set_mute_role(pool, guild_id.get(), Some(role.id.get())).await?;
+    send_config_updated(ctx, "Mute Role", role.mention().to_string()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_parse() {
+        assert_eq!(parse_duration("10m"), Some(Duration::from_secs(600)));
+        assert_eq!(parse_duration("1d"), Some(Duration::from_secs(86400)));
+        assert_eq!(parse_duration(" 2H "), Some(Duration::from_secs(7200)));
+    }
+
+    #[test]
+    fn multibyte_unit_is_rejected_without_panicking() {
+        assert_eq!(parse_duration("5é"), None);
+        assert_eq!(parse_duration("é"), None);
+        assert_eq!(parse_duration(""), None);
+    }
+
+    #[test]
+    fn overflowing_durations_are_rejected() {
+        assert_eq!(parse_duration("18446744073709551615d"), None);
+        assert_eq!(parse_duration("99999999999999999999s"), None);
+        assert!(parse_duration("9999999999999999s").is_some_and(|d| d > MAX_MUTE));
+    }
+}
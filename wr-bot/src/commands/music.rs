@@ -1,13 +1,19 @@
 use crate::commands::Data;
+use crate::repository::GuildSettingsRepository;
+use crate::services::lyrics::{clean_track, get_lyrics_service};
 use crate::services::music::queue::QueuedTrack;
+use crate::utils::branding::branded_embed;
 use crate::utils::embed;
-use poise::serenity_prelude::{CreateEmbed, Mentionable};
+use crate::utils::pagination::paginate;
+use crate::utils::text::{EMBED_DESCRIPTION_MAX, EMBED_TITLE_MAX, split_pages, truncate_field};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, Mentionable};
 use std::time::Duration;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 type Context<'a> = poise::Context<'a, Data, Error>;
 
 async fn send_embed(ctx: Context<'_>, embed: CreateEmbed) -> Result<(), Error> {
+    let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
@@ -29,7 +35,7 @@ fn extract_video_id(url: &str) -> Option<String> {
     None
 }
 
-#[poise::command(slash_command, prefix_command, guild_only)]
+#[poise::command(slash_command, prefix_command, guild_only, category = "Music")]
 pub async fn join(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
     let guild = ctx.guild().ok_or("Cannot get server info")?.clone();
@@ -117,7 +123,7 @@ pub async fn join(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
-#[poise::command(slash_command, prefix_command, guild_only)]
+#[poise::command(slash_command, prefix_command, guild_only, category = "Music")]
 pub async fn leave(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
 
@@ -140,7 +146,7 @@ pub async fn leave(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
-#[poise::command(slash_command, prefix_command, guild_only)]
+#[poise::command(slash_command, prefix_command, guild_only, category = "Music")]
 pub async fn play(
     ctx: Context<'_>,
     #[description = "URL or song title"]
@@ -444,8 +450,8 @@ async fn show_search_results(
         .iter()
         .enumerate()
         .map(|(i, video)| {
-            let label = if video.title.len() > 95 {
-                format!("{}...", &video.title[..92])
+            let label = if video.title.chars().count() > 95 {
+                format!("{}...", video.title.chars().take(92).collect::<String>())
             } else {
                 video.title.clone()
             };
@@ -475,6 +481,7 @@ async fn show_search_results(
             "Select a song from the dropdown below • Expires in 60s",
         ))
         .color(embed::COLOR_MUSIC);
+    let search_embed = branded_embed(&ctx.data().db, ctx.guild_id(), search_embed).await;
 
     let reply = ctx
         .send(
@@ -529,16 +536,16 @@ async fn show_search_results(
             }
         }
         None => {
+            let embed = CreateEmbed::new()
+                .title("Selection Expired")
+                .description("No song was selected. Use `/play` again to search.")
+                .color(0x95a5a6);
+            let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
             let _ = reply
                 .edit(
                     ctx,
                     poise::CreateReply::default()
-                        .embed(
-                            CreateEmbed::new()
-                                .title("Selection Expired")
-                                .description("No song was selected. Use `/play` again to search.")
-                                .color(0x95a5a6),
-                        )
+                        .embed(embed)
                         .components(vec![]),
                 )
                 .await;
@@ -548,7 +555,7 @@ async fn show_search_results(
     Ok(())
 }
 
-#[poise::command(slash_command, prefix_command, guild_only)]
+#[poise::command(slash_command, prefix_command, guild_only, category = "Music")]
 pub async fn pause(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
     let player = ctx
@@ -572,7 +579,7 @@ pub async fn pause(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
-#[poise::command(slash_command, prefix_command, guild_only)]
+#[poise::command(slash_command, prefix_command, guild_only, category = "Music")]
 pub async fn resume(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
     let player = ctx
@@ -596,7 +603,119 @@ pub async fn resume(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
-#[poise::command(slash_command, prefix_command, guild_only)]
+/// Skip votes are only needed when more than this many people are listening.
+const SKIP_VOTE_MIN_LISTENERS: usize = 2;
+const SKIP_VOTE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Non-bot users in the bot's voice channel.
+fn voice_listeners(
+    ctx: Context<'_>,
+    guild_id: serenity::all::GuildId,
+) -> Vec<serenity::all::UserId> {
+    let Some(guild) = ctx.cache().guild(guild_id) else {
+        return Vec::new();
+    };
+    let bot_id = ctx.framework().bot_id;
+    let Some(channel_id) = guild.voice_states.get(&bot_id).and_then(|vs| vs.channel_id) else {
+        return Vec::new();
+    };
+
+    guild
+        .voice_states
+        .values()
+        .filter(|vs| vs.channel_id == Some(channel_id) && vs.user_id != bot_id)
+        .filter(|vs| {
+            vs.member
+                .as_ref()
+                .map(|m| !m.user.bot)
+                .or_else(|| guild.members.get(&vs.user_id).map(|m| !m.user.bot))
+                .unwrap_or(true)
+        })
+        .map(|vs| vs.user_id)
+        .collect()
+}
+
+/// Whether the author has Administrator or Manage Server here.
+async fn author_is_admin(ctx: Context<'_>) -> bool {
+    let Some(member) = ctx.author_member().await else {
+        return false;
+    };
+    // Only interaction members carry resolved permissions
+    let permissions = member.permissions.or_else(|| {
+        let guild = ctx.guild()?;
+        let channel = guild.channels.get(&ctx.channel_id())?;
+        Some(guild.user_permissions_in(channel, &member))
+    });
+    permissions.is_some_and(|p| p.administrator() || p.manage_guild())
+}
+
+/// The guild's DJ role and whether being alone with the bot also counts.
+async fn dj_settings(
+    ctx: Context<'_>,
+    guild_id: serenity::all::GuildId,
+) -> Result<Option<(serenity::all::RoleId, bool)>, Error> {
+    let settings = GuildSettingsRepository::get(ctx.data().db.as_ref(), guild_id.get()).await?;
+    Ok(settings.and_then(|s| {
+        s.dj_role_id
+            .map(|id| (serenity::all::RoleId::new(id as u64), s.dj_alone_bypass))
+    }))
+}
+
+/// Whether the author may use disruptive music commands. Without a DJ role
+/// everyone may; otherwise admins, DJ role holders and, if enabled, someone
+/// alone with the bot.
+async fn is_dj(ctx: Context<'_>, guild_id: serenity::all::GuildId) -> Result<bool, Error> {
+    let Some((role_id, alone_bypass)) = dj_settings(ctx, guild_id).await? else {
+        return Ok(true);
+    };
+
+    if author_is_admin(ctx).await {
+        return Ok(true);
+    }
+    if ctx
+        .author_member()
+        .await
+        .is_some_and(|m| m.roles.contains(&role_id))
+    {
+        return Ok(true);
+    }
+
+    Ok(alone_bypass && voice_listeners(ctx, guild_id) == [ctx.author().id])
+}
+
+/// Replies with an error and returns false when the author is not a DJ.
+async fn require_dj(ctx: Context<'_>, guild_id: serenity::all::GuildId) -> Result<bool, Error> {
+    if is_dj(ctx, guild_id).await? {
+        return Ok(true);
+    }
+    send_embed(
+        ctx,
+        embed::error("DJ Only", "You need the DJ role to use this command"),
+    )
+    .await?;
+    Ok(false)
+}
+
+fn skip_vote_reply(
+    prefix: &str,
+    title: &str,
+    votes: usize,
+    required: usize,
+) -> (CreateEmbed, Vec<serenity::all::CreateActionRow>) {
+    let embed = embed::music(
+        "Vote to Skip",
+        &format!("Skip **{}**?\n\nVotes: **{}/{}**", title, votes, required),
+    );
+    let button = serenity::all::CreateButton::new(format!("{}_vote", prefix))
+        .label("Vote skip")
+        .style(serenity::all::ButtonStyle::Primary);
+    (
+        embed,
+        vec![serenity::all::CreateActionRow::Buttons(vec![button])],
+    )
+}
+
+#[poise::command(slash_command, prefix_command, guild_only, category = "Music")]
 pub async fn skip(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
     let player = ctx
@@ -605,6 +724,154 @@ pub async fn skip(ctx: Context<'_>) -> Result<(), Error> {
         .as_ref()
         .ok_or("Music player not available")?;
 
+    // With a DJ role set, skip is DJ-only like stop, volume and loop
+    if dj_settings(ctx, guild_id).await?.is_some() {
+        if !require_dj(ctx, guild_id).await? {
+            return Ok(());
+        }
+        return skip_now(ctx, player, guild_id).await;
+    }
+
+    let (Some(_), Some(current)) = (
+        player.get_player_context(guild_id),
+        player.get_current(guild_id),
+    ) else {
+        return skip_now(ctx, player, guild_id).await;
+    };
+
+    // Otherwise requesters and admins skip directly, and small audiences need no vote
+    let listeners = voice_listeners(ctx, guild_id);
+    let forced = current.requester_id == ctx.author().id.get() || author_is_admin(ctx).await;
+    if forced || listeners.len() <= SKIP_VOTE_MIN_LISTENERS {
+        return skip_now(ctx, player, guild_id).await;
+    }
+
+    if !listeners.contains(&ctx.author().id) {
+        send_embed(
+            ctx,
+            embed::error("Not Listening", "Join the bot's voice channel to vote"),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let required = listeners.len() / 2 + 1;
+    let votes = player.add_skip_vote(guild_id, ctx.author().id.get());
+    if votes >= required {
+        return skip_now(ctx, player, guild_id).await;
+    }
+
+    let title = current.track.info.title.clone();
+    let prefix = format!("skipvote_{}", ctx.id());
+    let (embed, components) = skip_vote_reply(&prefix, &title, votes, required);
+    let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    let reply = ctx
+        .send(
+            poise::CreateReply::default()
+                .embed(embed)
+                .components(components),
+        )
+        .await?;
+
+    loop {
+        let filter_prefix = prefix.clone();
+        let Some(interaction) =
+            serenity::all::ComponentInteractionCollector::new(ctx.serenity_context().shard.clone())
+                .channel_id(ctx.channel_id())
+                .filter(move |i| i.data.custom_id.starts_with(&filter_prefix))
+                .timeout(SKIP_VOTE_TIMEOUT)
+                .await
+        else {
+            let embed = embed::info("Vote Expired", "Not enough votes to skip");
+            let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+            let _ = reply
+                .edit(
+                    ctx,
+                    poise::CreateReply::default()
+                        .embed(embed)
+                        .components(vec![]),
+                )
+                .await;
+            return Ok(());
+        };
+
+        // Votes belong to the track they were cast for
+        let still_current = player
+            .get_current(guild_id)
+            .is_some_and(|t| t.track.info.identifier == current.track.info.identifier);
+        if !still_current {
+            let embed = embed::info("Vote Ended", "The track has already changed");
+            let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+            interaction
+                .create_response(
+                    ctx.http(),
+                    serenity::all::CreateInteractionResponse::UpdateMessage(
+                        serenity::all::CreateInteractionResponseMessage::new()
+                            .embed(embed)
+                            .components(vec![]),
+                    ),
+                )
+                .await?;
+            return Ok(());
+        }
+
+        let listeners = voice_listeners(ctx, guild_id);
+        if !listeners.contains(&interaction.user.id) {
+            interaction
+                .create_response(
+                    ctx.http(),
+                    serenity::all::CreateInteractionResponse::Message(
+                        serenity::all::CreateInteractionResponseMessage::new()
+                            .content("Join the bot's voice channel to vote.")
+                            .ephemeral(true),
+                    ),
+                )
+                .await?;
+            continue;
+        }
+
+        let required = listeners.len() / 2 + 1;
+        let votes = player.add_skip_vote(guild_id, interaction.user.id.get());
+
+        if votes >= required {
+            let embed = embed::music(
+                "Vote Passed",
+                &format!("Skipping **{}** ({}/{})", title, votes, required),
+            );
+            let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+            interaction
+                .create_response(
+                    ctx.http(),
+                    serenity::all::CreateInteractionResponse::UpdateMessage(
+                        serenity::all::CreateInteractionResponseMessage::new()
+                            .embed(embed)
+                            .components(vec![]),
+                    ),
+                )
+                .await?;
+            return skip_now(ctx, player, guild_id).await;
+        }
+
+        let (embed, components) = skip_vote_reply(&prefix, &title, votes, required);
+        let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+        interaction
+            .create_response(
+                ctx.http(),
+                serenity::all::CreateInteractionResponse::UpdateMessage(
+                    serenity::all::CreateInteractionResponseMessage::new()
+                        .embed(embed)
+                        .components(components),
+                ),
+            )
+            .await?;
+    }
+}
+
+async fn skip_now(
+    ctx: Context<'_>,
+    player: &crate::services::music::MusicPlayer,
+    guild_id: serenity::all::GuildId,
+) -> Result<(), Error> {
     if let Some(player_ctx) = player.get_player_context(guild_id) {
         if let Some(next_track) = player.next_track(guild_id) {
             // Save track title for autoplay
@@ -650,7 +917,7 @@ pub async fn skip(ctx: Context<'_>) -> Result<(), Error> {
                             .await?;
                     } else {
                         let channel_id = ctx.channel_id();
-                        let mut embed_msg = CreateEmbed::new()
+                        let embed_msg = CreateEmbed::new()
                             .title("🔄 Autoplay")
                             .description(format!(
                                 "**[{}]({})**\nby {}",
@@ -662,6 +929,8 @@ pub async fn skip(ctx: Context<'_>) -> Result<(), Error> {
                             .footer(serenity::all::CreateEmbedFooter::new(
                                 "Use /autoplay to disable",
                             ));
+                        let mut embed_msg =
+                            branded_embed(&ctx.data().db, ctx.guild_id(), embed_msg).await;
 
                         // Add thumbnail if available
                         if let Some(ref artwork) = track.info.artwork_url {
@@ -759,9 +1028,12 @@ async fn search_autoplay_track(
     }
 }
 
-#[poise::command(slash_command, prefix_command, guild_only)]
+#[poise::command(slash_command, prefix_command, guild_only, category = "Music")]
 pub async fn stop(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
+    if !require_dj(ctx, guild_id).await? {
+        return Ok(());
+    }
     let player = ctx
         .data()
         .music_player
@@ -784,7 +1056,7 @@ pub async fn stop(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
-#[poise::command(slash_command, prefix_command, guild_only)]
+#[poise::command(slash_command, prefix_command, guild_only, category = "Music")]
 pub async fn queue(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
     let player = ctx
@@ -858,7 +1130,13 @@ pub async fn queue(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
-#[poise::command(slash_command, prefix_command, guild_only, aliases("np"))]
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    aliases("np"),
+    category = "Music"
+)]
 pub async fn nowplaying(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
     let player = ctx
@@ -872,34 +1150,157 @@ pub async fn nowplaying(ctx: Context<'_>) -> Result<(), Error> {
     match &queue.current {
         Some(current) => {
             let track_info = &current.track.info;
+            let mut embed = embed::now_playing(
+                &track_info.title,
+                &track_info.uri.clone().unwrap_or_default(),
+                &track_info.author,
+                &format_duration(track_info.length),
+                &current.requester_name,
+                queue.volume,
+                queue.is_looping,
+                track_info.artwork_url.as_deref(),
+            );
+
+            if let Some((elapsed, length)) = player.current_track_progress(guild_id).await {
+                let progress = match length {
+                    Some(length) => format!(
+                        "{} `{} / {}`",
+                        progress_bar(elapsed, length),
+                        format_duration(elapsed.as_millis() as u64),
+                        format_duration(length.as_millis() as u64)
+                    ),
+                    None => format!("🔴 Live `{}`", format_duration(elapsed.as_millis() as u64)),
+                };
+                embed = embed.field("Progress", progress, false);
+            }
+
+            send_embed(ctx, embed).await?;
+        }
+        None => {
             send_embed(
                 ctx,
-                embed::now_playing(
-                    &track_info.title,
-                    &track_info.uri.clone().unwrap_or_default(),
-                    &track_info.author,
-                    &format_duration(track_info.length),
-                    &current.requester_name,
-                    queue.volume,
-                    queue.is_looping,
-                    track_info.artwork_url.as_deref(),
-                ),
+                embed::error("Not Playing", "No song is currently playing"),
             )
             .await?;
         }
+    }
+
+    Ok(())
+}
+
+/// Show lyrics for the current track or a song you name
+#[poise::command(slash_command, prefix_command, guild_only, category = "Music")]
+pub async fn lyrics(
+    ctx: Context<'_>,
+    #[description = "Song to search (default: the current track)"]
+    #[rest]
+    query: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
+    let service = get_lyrics_service().ok_or("Lyrics service not available")?;
+
+    ctx.defer().await?;
+
+    let query = query
+        .map(|q| q.trim().to_string())
+        .filter(|q| !q.is_empty());
+    let (searched, result) = match query {
+        Some(query) => {
+            let result = service.search_query(&query).await;
+            (query, result)
+        }
         None => {
+            let current = ctx
+                .data()
+                .music_player
+                .as_ref()
+                .and_then(|player| player.get_current(guild_id));
+            let Some(current) = current else {
+                send_embed(
+                    ctx,
+                    embed::error(
+                        "Nothing Playing",
+                        "Play a track first or pass a song to search for.",
+                    ),
+                )
+                .await?;
+                return Ok(());
+            };
+
+            let info = &current.track.info;
+            let (title, artist) = clean_track(&info.title, &info.author);
+            let mut result = service.search_track(&title, artist.as_deref()).await;
+
+            // Channel names are often not the real artist, so retry loosely
+            if let (Ok(None), Some(artist)) = (&result, &artist) {
+                result = service.search_query(&format!("{} {}", artist, title)).await;
+            }
+            (info.title.clone(), result)
+        }
+    };
+
+    let lyrics = match result {
+        Ok(Some(lyrics)) => lyrics,
+        Ok(None) => {
             send_embed(
                 ctx,
-                embed::error("Not Playing", "No song is currently playing"),
+                embed::warning(
+                    "No Lyrics Found",
+                    &format!("Couldn't find lyrics for **{}**.", searched),
+                ),
             )
             .await?;
+            return Ok(());
         }
+        Err(e) => {
+            eprintln!("[WARN] Lyrics lookup failed: {}", e);
+            send_embed(
+                ctx,
+                embed::error(
+                    "Lyrics Unavailable",
+                    "The lyrics provider is not responding right now. Please try again later.",
+                ),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let title = truncate_field(
+        &format!("🎤 {} - {}", lyrics.artist, lyrics.title),
+        EMBED_TITLE_MAX,
+    );
+
+    if lyrics.text.is_empty() {
+        send_embed(ctx, embed::music(&title, "This track is instrumental.")).await?;
+        return Ok(());
     }
 
-    Ok(())
+    let pages = split_pages(&lyrics.text, EMBED_DESCRIPTION_MAX);
+    let total = pages.len();
+
+    let pool = ctx.data().db.clone();
+    let guild_id = ctx.guild_id();
+
+    paginate(ctx, move |page| {
+        let pool = pool.clone();
+        let embed = pages.get(page).map(|text| {
+            embed::music(&title, text).footer(CreateEmbedFooter::new(format!(
+                "Page {}/{} • Lyrics from LRCLIB",
+                page + 1,
+                total
+            )))
+        });
+        async move {
+            let embed = embed.ok_or("Lyrics page out of range")?;
+            let embed = branded_embed(&pool, guild_id, embed).await;
+            Ok((embed, page + 1 < total))
+        }
+    })
+    .await
 }
 
-#[poise::command(slash_command, prefix_command, guild_only)]
+#[poise::command(slash_command, prefix_command, guild_only, category = "Music")]
 pub async fn volume(
     ctx: Context<'_>,
     #[description = "Volume (0-150)"]
@@ -908,6 +1309,9 @@ pub async fn volume(
     level: u8,
 ) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
+    if !require_dj(ctx, guild_id).await? {
+        return Ok(());
+    }
     let player = ctx
         .data()
         .music_player
@@ -945,7 +1349,8 @@ pub async fn volume(
     prefix_command,
     guild_only,
     rename = "repeat",
-    aliases("r")
+    aliases("r"),
+    category = "Music"
 )]
 pub async fn repeat(
     ctx: Context<'_>,
@@ -954,6 +1359,9 @@ pub async fn repeat(
     use crate::services::music::queue::LoopMode;
 
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
+    if !require_dj(ctx, guild_id).await? {
+        return Ok(());
+    }
     let player = ctx
         .data()
         .music_player
@@ -997,7 +1405,7 @@ pub async fn repeat(
     Ok(())
 }
 
-#[poise::command(slash_command, prefix_command, guild_only)]
+#[poise::command(slash_command, prefix_command, guild_only, category = "Music")]
 pub async fn shuffle(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
     let player = ctx
@@ -1016,7 +1424,7 @@ pub async fn shuffle(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
-#[poise::command(slash_command, prefix_command, guild_only)]
+#[poise::command(slash_command, prefix_command, guild_only, category = "Music")]
 pub async fn remove(
     ctx: Context<'_>,
     #[description = "Position in queue (1, 2, 3, ...)"] position: usize,
@@ -1056,7 +1464,7 @@ pub async fn remove(
     Ok(())
 }
 
-#[poise::command(slash_command, prefix_command, guild_only)]
+#[poise::command(slash_command, prefix_command, guild_only, category = "Music")]
 pub async fn autoplay(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
     let player = ctx
@@ -1092,6 +1500,133 @@ pub async fn autoplay(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+const PROGRESS_BAR_WIDTH: usize = 15;
+
+fn progress_bar(elapsed: Duration, length: Duration) -> String {
+    let ratio = if length.is_zero() {
+        0.0
+    } else {
+        (elapsed.as_secs_f64() / length.as_secs_f64()).clamp(0.0, 1.0)
+    };
+    let knob = ((ratio * PROGRESS_BAR_WIDTH as f64) as usize).min(PROGRESS_BAR_WIDTH - 1);
+
+    (0..PROGRESS_BAR_WIDTH)
+        .map(|i| if i == knob { "🔘" } else { "▬" })
+        .collect()
+}
+
+/// Parses `ss`, `mm:ss` or `hh:mm:ss`.
+fn parse_timestamp(input: &str) -> Option<Duration> {
+    let parts = input
+        .trim()
+        .split(':')
+        .map(|p| p.trim().parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    let secs = match parts.as_slice() {
+        [s] => *s,
+        [m, s] if *s < 60 => m.checked_mul(60)?.checked_add(*s)?,
+        [h, m, s] if *m < 60 && *s < 60 => h.checked_mul(3600)?.checked_add(m * 60 + s)?,
+        _ => return None,
+    };
+    Some(Duration::from_secs(secs))
+}
+
+#[poise::command(slash_command, prefix_command, guild_only, category = "Music")]
+pub async fn seek(
+    ctx: Context<'_>,
+    #[description = "Position to jump to (mm:ss)"] position: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
+    let player = ctx
+        .data()
+        .music_player
+        .as_ref()
+        .ok_or("Music player not available")?;
+
+    let Some(target) = parse_timestamp(&position) else {
+        send_embed(
+            ctx,
+            embed::error(
+                "Invalid Position",
+                "Use a timestamp like `1:30` or `01:02:03`",
+            ),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    match player.seek(guild_id, target).await {
+        Ok(length) => {
+            let position_ms = target.as_millis() as u64;
+            let remaining_ms = length.saturating_sub(target).as_millis() as u64;
+            send_embed(
+                ctx,
+                embed::music(
+                    "Seeked",
+                    &format!(
+                        "Jumped to **{}** ({} remaining)",
+                        format_duration(position_ms),
+                        format_duration(remaining_ms)
+                    ),
+                ),
+            )
+            .await?;
+        }
+        Err(e) => {
+            send_embed(ctx, embed::error("Cannot Seek", &e)).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Restrict skip, stop, volume and repeat to a DJ role
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    category = "Music"
+)]
+pub async fn setdj(
+    ctx: Context<'_>,
+    #[description = "DJ role (leave empty to allow everyone)"] role: Option<serenity::all::Role>,
+    #[description = "Let someone alone with the bot act as DJ (default: yes)"] alone_bypass: Option<
+        bool,
+    >,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
+    let alone_bypass = alone_bypass.unwrap_or(true);
+
+    GuildSettingsRepository::set_dj_role(
+        ctx.data().db.as_ref(),
+        guild_id.get(),
+        role.as_ref().map(|r| r.id.get()),
+        alone_bypass,
+    )
+    .await?;
+
+    let embed = match role {
+        Some(role) => embed::success(
+            "DJ Role Set",
+            &format!(
+                "Only admins and {} can skip, stop, change volume or repeat.{}",
+                role.mention(),
+                if alone_bypass {
+                    " Anyone alone with the bot counts as a DJ."
+                } else {
+                    ""
+                }
+            ),
+        ),
+        None => embed::success("DJ Role Cleared", "Everyone can use all music commands."),
+    };
+    send_embed(ctx, embed).await?;
+
+    Ok(())
+}
+
 fn format_duration(ms: u64) -> String {
     let duration = Duration::from_millis(ms);
     let secs = duration.as_secs();
@@ -1106,3 +1641,22 @@ fn format_duration(ms: u64) -> String {
         format!("{:02}:{:02}", mins, secs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamps_parse() {
+        assert_eq!(parse_timestamp("45"), Some(Duration::from_secs(45)));
+        assert_eq!(parse_timestamp("1:30"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_timestamp("01:02:03"), Some(Duration::from_secs(3723)));
+        assert_eq!(parse_timestamp("1:60"), None);
+    }
+
+    #[test]
+    fn overflowing_timestamps_are_rejected() {
+        assert_eq!(parse_timestamp("18446744073709551615:00"), None);
+        assert_eq!(parse_timestamp("9999999999999999999:00:00"), None);
+    }
+}
@@ -1,9 +1,67 @@
+use crate::repository::connection;
+use crate::utils::branding::branded_embed;
+use poise::serenity_prelude as serenity;
+use std::time::{Duration, Instant};
+
 type Error = Box<dyn std::error::Error + Send + Sync>;
 type Context<'a> = poise::Context<'a, super::Data, Error>;
 
+fn format_latency(latency: Option<Duration>) -> String {
+    match latency {
+        Some(d) => format!("`{} ms`", d.as_millis()),
+        None => "`N/A`".to_string(),
+    }
+}
+
+/// Show REST, gateway and database latency
 #[poise::command(slash_command, prefix_command)]
 pub async fn ping(ctx: Context<'_>) -> Result<(), Error> {
-    ctx.say("Pong!").await?;
+    ctx.defer().await?;
+
+    let started = Instant::now();
+    let reply = ctx.say("Pinging...").await?;
+    let rest = started.elapsed();
+
+    // Zero means the shard has not completed a heartbeat yet
+    let gateway = Some(ctx.ping().await).filter(|d| !d.is_zero());
+
+    let started = Instant::now();
+    let database = connection::ping(ctx.data().db.as_ref())
+        .await
+        .ok()
+        .map(|_| started.elapsed());
+
+    let worst = if database.is_none() {
+        None
+    } else {
+        [Some(rest), gateway, database].into_iter().flatten().max()
+    };
+
+    let color = match worst.map(|d| d.as_millis()) {
+        Some(ms) if ms < 150 => serenity::Colour::from_rgb(46, 204, 113),
+        Some(ms) if ms < 500 => serenity::Colour::from_rgb(241, 196, 15),
+        _ => serenity::Colour::from_rgb(231, 76, 60),
+    };
+
+    let embed = serenity::CreateEmbed::default()
+        .title("Pong!")
+        .field("REST", format_latency(Some(rest)), true)
+        .field("Gateway", format_latency(gateway), true)
+        .field(
+            "Database",
+            if database.is_some() {
+                format_latency(database)
+            } else {
+                "`Unreachable`".to_string()
+            },
+            true,
+        )
+        .color(color)
+        .timestamp(serenity::Timestamp::now());
+
+    let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    reply
+        .edit(ctx, poise::CreateReply::default().content("").embed(embed))
+        .await?;
     Ok(())
 }
-
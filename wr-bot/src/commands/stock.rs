@@ -1,44 +1,121 @@
 use crate::commands::Data;
-use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use crate::repository::{EmbedStyleRepository, StockEmbedStyle, StockRepository};
+use crate::services::stock_ws::{StockNewsData, StockNewsWsClient};
+use crate::utils::branding;
+use crate::utils::locale::Locale;
+use crate::utils::pagination::paginate;
+use crate::utils::permissions::can_post;
+use crate::utils::sql::contains_pattern;
+use crate::utils::text::{EMBED_TITLE_MAX, truncate_desc, truncate_field};
+use crate::utils::webhook::parse_webhook_url;
+use parking_lot::Mutex;
+use poise::serenity_prelude::{self as serenity, CreateEmbed, CreateEmbedFooter, Role};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 type Context<'a> = poise::Context<'a, Data, Error>;
 
+const TICKER_CACHE_TTL: Duration = Duration::from_secs(60);
+const TICKER_CACHE_SIZE: i64 = 500;
+
+static TICKER_CACHE: Mutex<Option<(Instant, Arc<Vec<String>>)>> = Mutex::new(None);
+
+/// Known tickers, refreshed from the database at most once per [`TICKER_CACHE_TTL`].
+async fn cached_tickers(ctx: Context<'_>) -> Arc<Vec<String>> {
+    if let Some((fetched, tickers)) = TICKER_CACHE.lock().as_ref()
+        && fetched.elapsed() < TICKER_CACHE_TTL
+    {
+        return tickers.clone();
+    }
+
+    match StockRepository::distinct_recent_tickers(ctx.data().db.as_ref(), TICKER_CACHE_SIZE).await
+    {
+        Ok(tickers) => {
+            let tickers = Arc::new(tickers);
+            *TICKER_CACHE.lock() = Some((Instant::now(), tickers.clone()));
+            tickers
+        }
+        Err(e) => {
+            eprintln!("[WARN] Failed to load tickers for autocomplete: {}", e);
+            // Keep serving the stale list rather than nothing
+            TICKER_CACHE
+                .lock()
+                .as_ref()
+                .map(|(_, tickers)| tickers.clone())
+                .unwrap_or_default()
+        }
+    }
+}
+
+/// Drops the cached ticker list so the next autocomplete reloads it.
+pub fn clear_ticker_cache() -> bool {
+    TICKER_CACHE.lock().take().is_some()
+}
+
+async fn autocomplete_ticker(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let partial = partial.trim().to_uppercase();
+    cached_tickers(ctx)
+        .await
+        .iter()
+        .filter(|t| t.starts_with(&partial))
+        .take(25)
+        .cloned()
+        .collect()
+}
+
 /// Stock news commands
 #[poise::command(
     slash_command,
-    subcommands("subscribe", "unsubscribe", "status", "latest"),
-    subcommand_required
+    subcommands(
+        "subscribe",
+        "unsubscribe",
+        "status",
+        "latest",
+        "test",
+        "webhook",
+        "digest",
+        "mentionrole",
+        "usethread",
+        "stats"
+    ),
+    subcommand_required,
+    category = "Stock"
 )]
 pub async fn stocknews(_ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
 /// Subscribe this channel to Indonesian stock news alerts
-#[poise::command(slash_command, required_permissions = "MANAGE_CHANNELS")]
+#[poise::command(
+    slash_command,
+    required_permissions = "MANAGE_CHANNELS",
+    category = "Stock"
+)]
 pub async fn subscribe(
     ctx: Context<'_>,
     #[description = "Mention @everyone for high impact news"] mention_everyone: Option<bool>,
 ) -> Result<(), Error> {
+    if !can_post(ctx, ctx.channel_id()).await {
+        let embed = CreateEmbed::new()
+            .title("Tidak Bisa Mengirim")
+            .description(
+                "Bot membutuhkan izin **Send Messages** dan **Embed Links** di channel teks ini.",
+            )
+            .color(0xFF0000);
+        let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
     let pool = ctx.data().db.as_ref();
     
-    let channel_id = ctx.channel_id().get() as i64;
-    let guild_id = ctx.guild_id().map(|g| g.get() as i64).unwrap_or(0);
+    let channel_id = ctx.channel_id().get();
+    let guild_id = ctx.guild_id().map(|g| g.get()).unwrap_or(0);
     let mention = mention_everyone.unwrap_or(false);
     
-    sqlx::query(
-        r#"
-        INSERT INTO stock_news_channels (channel_id, guild_id, mention_everyone, is_active)
-        VALUES ($1, $2, $3, TRUE)
-        ON CONFLICT (channel_id) DO UPDATE
-        SET mention_everyone = $3, is_active = TRUE, updated_at = NOW()
-        "#,
-    )
-    .bind(channel_id)
-    .bind(guild_id)
-    .bind(mention)
-    .execute(pool)
-    .await?;
+    StockRepository::subscribe(pool, guild_id, channel_id, mention).await?;
     
     let embed = CreateEmbed::new()
         .title("Stock News Alert Aktif")
@@ -48,12 +125,17 @@ pub async fn subscribe(
         .color(0x00FF00)
         .footer(CreateEmbedFooter::new("Gunakan /stocknews unsubscribe untuk berhenti"));
     
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
 
 /// Unsubscribe this channel from stock news alerts
-#[poise::command(slash_command, required_permissions = "MANAGE_CHANNELS")]
+#[poise::command(
+    slash_command,
+    required_permissions = "MANAGE_CHANNELS",
+    category = "Stock"
+)]
 pub async fn unsubscribe(ctx: Context<'_>) -> Result<(), Error> {
     let pool = ctx.data().db.as_ref();
     
@@ -78,12 +160,13 @@ pub async fn unsubscribe(ctx: Context<'_>) -> Result<(), Error> {
             .color(0xFF0000)
     };
     
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
 
 /// Check stock news subscription status
-#[poise::command(slash_command)]
+#[poise::command(slash_command, category = "Stock")]
 pub async fn status(ctx: Context<'_>) -> Result<(), Error> {
     let pool = ctx.data().db.as_ref();
     
@@ -114,38 +197,299 @@ pub async fn status(ctx: Context<'_>) -> Result<(), Error> {
         }
     };
     
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
 
-/// Get latest Indonesian stock news
-#[poise::command(slash_command)]
-pub async fn latest(
+/// Send a sample stock news alert to this channel
+#[poise::command(
+    slash_command,
+    required_permissions = "MANAGE_CHANNELS",
+    category = "Stock"
+)]
+pub async fn test(ctx: Context<'_>) -> Result<(), Error> {
+    let pool = ctx.data().db.as_ref();
+
+    let channel = StockRepository::get_channel(pool, ctx.channel_id().get()).await?;
+
+    let sample = StockNewsData {
+        id: "test-preview".to_string(),
+        title: "[CONTOH] BBCA catat laba bersih naik 12% secara tahunan".to_string(),
+        summary: Some("Ini adalah contoh berita untuk pratinjau tampilan alert.".to_string()),
+        content: None,
+        source_name: "CNBC Indonesia".to_string(),
+        source_url: "https://www.cnbcindonesia.com".to_string(),
+        original_url: "https://www.cnbcindonesia.com/market".to_string(),
+        category: "emiten".to_string(),
+        tickers: vec!["BBCA".to_string()],
+        sentiment: Some("bullish".to_string()),
+        impact_level: Some("high".to_string()),
+        published_at: Some(chrono::Utc::now().to_rfc3339()),
+        processed_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    let style = match ctx.guild_id() {
+        Some(guild_id) => EmbedStyleRepository::get(pool, guild_id.get()).await?,
+        None => None,
+    };
+    let embed = StockNewsWsClient::build_stock_embed(&sample, style.as_ref());
+
+    let note = match &channel {
+        Some(ch) if ch.is_active => {
+            let filters = if StockNewsWsClient::passes_filters(
+                ch,
+                &sample.tickers,
+                &sample.category,
+                sample.impact_level.as_deref(),
+            ) {
+                "Berita ini **lolos** filter channel ini."
+            } else {
+                "Berita ini **tidak lolos** filter channel ini."
+            };
+            let mention = if ch.mention_everyone {
+                "Berita high impact akan disertai @everyone."
+            } else {
+                "Berita high impact tidak akan mention @everyone."
+            };
+            format!("{}\n{}", filters, mention)
+        }
+        _ => "Channel ini belum berlangganan. Gunakan `/stocknews subscribe`.".to_string(),
+    };
+
+    // Left unbranded so the preview matches what the news feed posts
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!(
+                "**PRATINJAU TES - bukan berita sungguhan**\n{}",
+                note
+            ))
+            .embed(embed),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Deliver stock news in this channel through a webhook (leave empty to clear)
+#[poise::command(
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    category = "Stock"
+)]
+pub async fn webhook(
     ctx: Context<'_>,
-    #[description = "Filter by ticker (e.g. BBCA, BBRI)"] ticker: Option<String>,
-    #[description = "Number of news to show (max 10)"] limit: Option<i64>,
+    #[description = "Discord webhook URL"] url: Option<String>,
 ) -> Result<(), Error> {
-    ctx.defer().await?;
-    
     let pool = ctx.data().db.as_ref();
-    let limit = limit.unwrap_or(5).min(10);
-    
-    // Query latest stock news
-    let rows: Vec<(String, String, Option<String>, Option<String>, String, Option<String>, Option<String>, Option<String>, Option<chrono::NaiveDateTime>)> = if let Some(ticker) = ticker {
-        let ticker_upper = ticker.to_uppercase();
+
+    if let Some(url) = &url
+        && parse_webhook_url(url).is_none()
+    {
+        let embed = CreateEmbed::new()
+            .title("Webhook Tidak Valid")
+            .description("URL harus berupa webhook Discord (https://discord.com/api/webhooks/...).")
+            .color(0xFF0000);
+        let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    let updated =
+        StockRepository::set_webhook(pool, ctx.channel_id().get(), url.as_deref().map(str::trim))
+            .await?;
+
+    let embed = if !updated {
+        CreateEmbed::new()
+            .title("Belum Berlangganan")
+            .description(
+                "Channel ini belum berlangganan. Gunakan `/stocknews subscribe` terlebih dahulu.",
+            )
+            .color(0x808080)
+    } else if url.is_some() {
+        CreateEmbed::new()
+            .title("Webhook Diatur")
+            .description("Alert berita saham untuk channel ini akan dikirim melalui webhook.")
+            .color(0x00FF00)
+    } else {
+        CreateEmbed::new()
+            .title("Webhook Dihapus")
+            .description("Alert berita saham akan dikirim langsung oleh bot.")
+            .color(0xFF6600)
+    };
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+/// Post stock news as a periodic digest instead of one message per article
+#[poise::command(
+    slash_command,
+    required_permissions = "MANAGE_CHANNELS",
+    category = "Stock"
+)]
+pub async fn digest(
+    ctx: Context<'_>,
+    #[description = "Enable digest mode"] enabled: bool,
+    #[description = "Minutes between digests (5-1440, default 60)"] interval_minutes: Option<i32>,
+) -> Result<(), Error> {
+    let pool = ctx.data().db.as_ref();
+    let interval = interval_minutes.unwrap_or(60).clamp(5, 1440);
+
+    let updated =
+        StockRepository::set_digest(pool, ctx.channel_id().get(), enabled, interval).await?;
+
+    let embed = if !updated {
+        CreateEmbed::new()
+            .title("Belum Berlangganan")
+            .description(
+                "Channel ini belum berlangganan. Gunakan `/stocknews subscribe` terlebih dahulu.",
+            )
+            .color(0x808080)
+    } else if enabled {
+        CreateEmbed::new()
+            .title("Mode Ringkasan Aktif")
+            .description(format!(
+                "Berita saham akan dikumpulkan dan dikirim sebagai ringkasan setiap {} menit.",
+                interval
+            ))
+            .color(0x00FF00)
+    } else {
+        CreateEmbed::new()
+            .title("Mode Ringkasan Dinonaktifkan")
+            .description("Berita saham akan dikirim satu per satu seperti biasa.")
+            .color(0xFF6600)
+    };
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Ping a role instead of @everyone for high impact stock news
+#[poise::command(
+    slash_command,
+    required_permissions = "MANAGE_CHANNELS",
+    category = "Stock"
+)]
+pub async fn mentionrole(
+    ctx: Context<'_>,
+    #[description = "Role to ping (leave empty to clear)"] role: Option<Role>,
+) -> Result<(), Error> {
+    let pool = ctx.data().db.as_ref();
+    let role_id = role.as_ref().map(|r| r.id.get());
+
+    let updated = StockRepository::set_mention_role(pool, ctx.channel_id().get(), role_id).await?;
+
+    let embed = if !updated {
+        CreateEmbed::new()
+            .title("Belum Berlangganan")
+            .description(
+                "Channel ini belum berlangganan. Gunakan `/stocknews subscribe` terlebih dahulu.",
+            )
+            .color(0x808080)
+    } else if let Some(role) = role {
+        CreateEmbed::new()
+            .title("Role Mention Diatur")
+            .description(format!(
+                "Berita saham penting akan mention <@&{}>.",
+                role.id
+            ))
+            .color(0x00FF00)
+    } else {
+        CreateEmbed::new()
+            .title("Role Mention Dihapus")
+            .description(
+                "Berita saham penting akan kembali memakai pengaturan @everyone channel ini.",
+            )
+            .color(0xFF6600)
+    };
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Post stock news into a thread under this channel
+#[poise::command(
+    slash_command,
+    required_permissions = "MANAGE_CHANNELS",
+    category = "Stock"
+)]
+pub async fn usethread(
+    ctx: Context<'_>,
+    #[description = "Post news into a thread"] enabled: bool,
+) -> Result<(), Error> {
+    let pool = ctx.data().db.as_ref();
+
+    let updated = StockRepository::set_use_thread(pool, ctx.channel_id().get(), enabled).await?;
+
+    let embed = if !updated {
+        CreateEmbed::new()
+            .title("Belum Berlangganan")
+            .description(
+                "Channel ini belum berlangganan. Gunakan `/stocknews subscribe` terlebih dahulu.",
+            )
+            .color(0x808080)
+    } else if enabled {
+        CreateEmbed::new()
+            .title("Mode Thread Aktif")
+            .description(
+                "Berita saham akan dikirim ke thread di channel ini. Thread dibuat otomatis dan dibuat ulang jika diarsipkan. \
+                 Jika bot tidak punya izin membuat thread, berita tetap dikirim ke channel.",
+            )
+            .color(0x00FF00)
+    } else {
+        CreateEmbed::new()
+            .title("Mode Thread Dinonaktifkan")
+            .description("Berita saham akan dikirim langsung ke channel ini.")
+            .color(0xFF6600)
+    };
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+type LatestRow = (
+    String,
+    String,
+    Option<String>,
+    Option<String>,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<chrono::NaiveDateTime>,
+);
+
+const SUMMARY_MAX: usize = 200;
+
+/// Fetches one page of processed stock news, plus one extra row so the caller
+/// can tell whether another page exists.
+async fn fetch_latest(
+    pool: &sqlx::PgPool,
+    ticker: Option<&str>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<LatestRow>, sqlx::Error> {
+    if let Some(ticker) = ticker {
         sqlx::query_as(
             r#"
             SELECT content_hash, title, summary, source_name, original_url, category, sentiment, impact_level, published_at
             FROM stock_news
             WHERE is_processed = TRUE AND $1 = ANY(string_to_array(tickers, ','))
             ORDER BY published_at DESC NULLS LAST
-            LIMIT $2
+            LIMIT $2 OFFSET $3
             "#,
         )
-        .bind(ticker_upper)
-        .bind(limit)
+        .bind(ticker.to_uppercase())
+        .bind(limit + 1)
+        .bind(offset)
         .fetch_all(pool)
-        .await?
+        .await
     } else {
         sqlx::query_as(
             r#"
@@ -153,60 +497,104 @@ pub async fn latest(
             FROM stock_news
             WHERE is_processed = TRUE
             ORDER BY published_at DESC NULLS LAST
-            LIMIT $1
+            LIMIT $1 OFFSET $2
             "#,
         )
-        .bind(limit)
+        .bind(limit + 1)
+        .bind(offset)
         .fetch_all(pool)
-        .await?
-    };
-    
+        .await
+    }
+}
+
+fn latest_embed(rows: &[LatestRow], offset: usize, detailed: bool) -> CreateEmbed {
     if rows.is_empty() {
-        let embed = CreateEmbed::new()
+        return CreateEmbed::new()
             .title("Tidak Ada Berita")
             .description("Belum ada berita saham Indonesia yang tersedia.")
             .color(0x808080);
-        ctx.send(poise::CreateReply::default().embed(embed)).await?;
-        return Ok(());
     }
-    
-    // Build embed with news list
+
     let mut description = String::new();
-    
-    for (i, (_, title, _summary, source_name, url, _category, sentiment, _impact, published_at)) in rows.iter().enumerate() {
+
+    for (i, (_, title, summary, source_name, url, _category, sentiment, _impact, published_at)) in
+        rows.iter().enumerate()
+    {
         let sentiment_icon = match sentiment.as_deref() {
             Some("bullish") => "+",
             Some("bearish") => "-",
             _ => " ",
         };
-        
+
         let time_str = published_at
             .map(|t| t.format("%H:%M").to_string())
             .unwrap_or_default();
-        
+
         description.push_str(&format!(
-            "**{}. [{}]({})** {}\n{} | {}\n\n",
-            i + 1,
+            "**{}. [{}]({})** {}\n{} | {}\n",
+            offset + i + 1,
             title,
             url,
             sentiment_icon,
             source_name.as_deref().unwrap_or("Unknown"),
             time_str
         ));
+
+        if detailed && let Some(summary) = summary.as_deref().filter(|s| !s.trim().is_empty()) {
+            description.push_str(&format!(
+                "> {}\n",
+                truncate_field(summary.trim(), SUMMARY_MAX)
+            ));
+        }
+
+        description.push('\n');
     }
-    
-    let embed = CreateEmbed::new()
+
+    CreateEmbed::new()
         .title("Berita Saham Indonesia Terbaru")
-        .description(description)
+        .description(truncate_desc(&description))
         .color(0x2962FF)
-        .footer(CreateEmbedFooter::new("Data dari CNBC Indonesia, Kontan, Bisnis Indonesia, dll"));
-    
-    ctx.send(poise::CreateReply::default().embed(embed)).await?;
-    Ok(())
+        .footer(CreateEmbedFooter::new(
+            "Data dari CNBC Indonesia, Kontan, Bisnis Indonesia, dll",
+        ))
+}
+
+/// Get latest Indonesian stock news
+#[poise::command(slash_command, category = "Stock")]
+pub async fn latest(
+    ctx: Context<'_>,
+    #[description = "Filter by ticker (e.g. BBCA, BBRI)"]
+    #[autocomplete = "autocomplete_ticker"]
+    ticker: Option<String>,
+    #[description = "Number of news per page (max 10)"] limit: Option<i64>,
+    #[description = "Include article summaries"] detailed: Option<bool>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let pool = ctx.data().db.clone();
+    let limit = limit.unwrap_or(5).clamp(1, 10);
+    let detailed = detailed.unwrap_or(false);
+
+    let guild_id = ctx.guild_id();
+
+    paginate(ctx, move |page| {
+        let pool = pool.clone();
+        let ticker = ticker.clone();
+        async move {
+            let offset = page as i64 * limit;
+            let mut rows = fetch_latest(pool.as_ref(), ticker.as_deref(), limit, offset).await?;
+            let has_next = rows.len() as i64 > limit;
+            rows.truncate(limit as usize);
+            let embed = latest_embed(&rows, offset as usize, detailed);
+            let embed = branding::branded_embed(&pool, guild_id, embed).await;
+            Ok((embed, has_next))
+        }
+    })
+    .await
 }
 
 /// Search stock news by keyword
-#[poise::command(slash_command)]
+#[poise::command(slash_command, category = "Stock")]
 pub async fn search(
     ctx: Context<'_>,
     #[description = "Keyword to search"] keyword: String,
@@ -216,13 +604,13 @@ pub async fn search(
     
     let pool = ctx.data().db.as_ref();
     let limit = limit.unwrap_or(5).min(10);
-    let search_pattern = format!("%{}%", keyword);
+    let search_pattern = contains_pattern(&keyword);
     
     let rows: Vec<(String, String, Option<String>, Option<String>, String)> = sqlx::query_as(
         r#"
         SELECT content_hash, title, summary, source_name, original_url
         FROM stock_news
-        WHERE is_processed = TRUE AND (title ILIKE $1 OR summary ILIKE $1)
+        WHERE is_processed = TRUE AND (title ILIKE $1 ESCAPE '\' OR summary ILIKE $1 ESCAPE '\')
         ORDER BY published_at DESC NULLS LAST
         LIMIT $2
         "#,
@@ -237,6 +625,7 @@ pub async fn search(
             .title("Tidak Ditemukan")
             .description(format!("Tidak ada berita dengan keyword \"{}\"", keyword))
             .color(0x808080);
+        let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
     }
@@ -249,25 +638,101 @@ pub async fn search(
             i + 1,
             title,
             url,
-            summary.as_deref().unwrap_or("").chars().take(150).collect::<String>()
+            truncate_field(summary.as_deref().unwrap_or(""), 150)
         ));
     }
     
     let embed = CreateEmbed::new()
-        .title(format!("Hasil Pencarian: {}", keyword))
-        .description(description)
+        .title(truncate_field(
+            &format!("Hasil Pencarian: {}", keyword),
+            EMBED_TITLE_MAX,
+        ))
+        .description(truncate_desc(&description))
         .color(0x2962FF);
     
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+const STATS_MAX_SOURCES: usize = 15;
+const STATS_BAR_WIDTH: i64 = 12;
+
+/// Show which sources published the most stock news
+#[poise::command(slash_command, category = "Stock")]
+pub async fn stats(
+    ctx: Context<'_>,
+    #[description = "Rentang waktu dalam jam (1-168, default 24)"]
+    #[min = 1]
+    #[max = 168]
+    hours: Option<i32>,
+) -> Result<(), Error> {
+    let hours = hours.unwrap_or(24).clamp(1, 168);
+
+    let pool = ctx.data().db.as_ref();
+    let locale = Locale::for_guild(pool, ctx.guild_id().map(|g| g.get())).await;
+    let counts = StockRepository::source_counts(pool, hours).await?;
+
+    let total: i64 = counts.iter().map(|(_, count)| count).sum();
+    let max = counts.first().map(|(_, count)| *count).unwrap_or(0);
+
+    let description = if counts.is_empty() {
+        format!("Tidak ada berita dalam {} jam terakhir", hours)
+    } else {
+        let mut lines = counts
+            .iter()
+            .take(STATS_MAX_SOURCES)
+            .enumerate()
+            .map(|(i, (source, count))| {
+                // At least one block so every listed source is visible
+                let filled = (count * STATS_BAR_WIDTH / max.max(1)).max(1);
+                format!(
+                    "`{:>2}.` `{}{}` **{}** - {}",
+                    i + 1,
+                    "█".repeat(filled as usize),
+                    "░".repeat((STATS_BAR_WIDTH - filled) as usize),
+                    source,
+                    locale.format_count(*count)
+                )
+            })
+            .collect::<Vec<_>>();
+        if counts.len() > STATS_MAX_SOURCES {
+            lines.push(format!(
+                "...dan {} sumber lainnya",
+                counts.len() - STATS_MAX_SOURCES
+            ));
+        }
+        lines.join("\n")
+    };
+
+    let embed = CreateEmbed::new()
+        .title(format!("Volume Berita per Sumber ({} Jam)", hours))
+        .description(description)
+        .color(0x2962FF)
+        .footer(CreateEmbedFooter::new(format!(
+            "Total {} berita dari {} sumber",
+            locale.format_count(total),
+            counts.len()
+        )));
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
 
 /// Get stock market summary
-#[poise::command(slash_command)]
+#[poise::command(slash_command, category = "Stock")]
 pub async fn market(ctx: Context<'_>) -> Result<(), Error> {
     ctx.defer().await?;
     
     let pool = ctx.data().db.as_ref();
+    let locale = Locale::for_guild(pool, ctx.guild_id().map(|g| g.get())).await;
+    let style = match ctx.guild_id() {
+        Some(guild_id) => EmbedStyleRepository::get(pool, guild_id.get())
+            .await?
+            .unwrap_or_default(),
+        None => StockEmbedStyle::default(),
+    };
     
     // Get recent high impact news
     let high_impact: Vec<(String, Option<String>)> = sqlx::query_as(
@@ -330,8 +795,8 @@ pub async fn market(ctx: Context<'_>) -> Result<(), Error> {
         high_impact.iter()
             .map(|(title, sentiment)| {
                 let icon = match sentiment.as_deref() {
-                    Some("bullish") => "+",
-                    Some("bearish") => "-",
+                    Some("bullish") => style.sentiment_marker(Some("bullish")).unwrap_or("+"),
+                    Some("bearish") => style.sentiment_marker(Some("bearish")).unwrap_or("-"),
                     _ => " ",
                 };
                 format!("{} {}", icon, title)
@@ -343,8 +808,8 @@ pub async fn market(ctx: Context<'_>) -> Result<(), Error> {
     let embed = CreateEmbed::new()
         .title("Ringkasan Pasar Saham Indonesia")
         .field("Sentimen 24 Jam", sentiment_indicator, true)
-        .field("Bullish", bullish.to_string(), true)
-        .field("Bearish", bearish.to_string(), true)
+        .field("Bullish", locale.format_count(bullish), true)
+        .field("Bearish", locale.format_count(bearish), true)
         .field("Berita High Impact (24 Jam)", high_impact_list, false)
         .color(match sentiment_indicator {
             "Bullish" => 0x00FF00,
@@ -353,6 +818,143 @@ pub async fn market(ctx: Context<'_>) -> Result<(), Error> {
         })
         .footer(CreateEmbedFooter::new("Update setiap 3 menit"));
     
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Rough single-grapheme check: one base character followed only by
+/// joiners, variation selectors, skin tones, keycaps, combining marks or tag
+/// characters, or a pair of regional indicators (flags).
+fn is_single_grapheme(text: &str) -> bool {
+    let mut chars = text.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+
+    let is_regional = |c: char| ('\u{1F1E6}'..='\u{1F1FF}').contains(&c);
+    let mut flag_pending = is_regional(first);
+    let mut after_joiner = false;
+
+    for c in chars {
+        if after_joiner {
+            after_joiner = false;
+            continue;
+        }
+        match c {
+            '\u{200D}' => after_joiner = true,
+            '\u{FE00}'..='\u{FE0F}'
+            | '\u{1F3FB}'..='\u{1F3FF}'
+            | '\u{20E3}'
+            | '\u{0300}'..='\u{036F}'
+            | '\u{E0020}'..='\u{E007F}' => {}
+            c if flag_pending && is_regional(c) => flag_pending = false,
+            _ => return false,
+        }
+    }
+
+    !after_joiner
+}
+
+/// Trims a marker and checks it is one emoji/character or a custom emoji mention.
+fn parse_marker(input: Option<String>) -> Result<Option<String>, String> {
+    let Some(input) = input else {
+        return Ok(None);
+    };
+    let marker = input.trim();
+    if is_single_grapheme(marker) || serenity::parse_emoji(marker).is_some() {
+        Ok(Some(marker.to_string()))
+    } else {
+        Err(format!(
+            "`{}` bukan satu emoji/karakter atau custom emoji (`<:nama:id>`)",
+            input
+        ))
+    }
+}
+
+/// Set the impact and sentiment markers used in stock news embeds
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    category = "Stock"
+)]
+pub async fn stockstyle(
+    ctx: Context<'_>,
+    #[description = "Penanda impact tinggi (default HIGH)"] high: Option<String>,
+    #[description = "Penanda impact sedang (default MED)"] medium: Option<String>,
+    #[description = "Penanda impact rendah (default LOW)"] low: Option<String>,
+    #[description = "Penanda sentimen bullish"] bullish: Option<String>,
+    #[description = "Penanda sentimen bearish"] bearish: Option<String>,
+    #[description = "Kembalikan semua penanda ke default"] reset: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let pool = ctx.data().db.as_ref();
+
+    if reset.unwrap_or(false) {
+        EmbedStyleRepository::reset(pool, guild_id).await?;
+        let embed = CreateEmbed::new()
+            .title("Gaya Embed Direset")
+            .description("Penanda stock news kembali ke default.")
+            .color(0x00FF00);
+        let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let parsed = [high, medium, low, bullish, bearish].map(parse_marker);
+    if let Some(Err(message)) = parsed.iter().find(|m| m.is_err()) {
+        let embed = CreateEmbed::new()
+            .title("Penanda Tidak Valid")
+            .description(message)
+            .color(0xFF0000);
+        let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+    let [impact_high, impact_medium, impact_low, bullish, bearish] =
+        parsed.map(|m| m.unwrap_or_default());
+    let update = StockEmbedStyle {
+        impact_high,
+        impact_medium,
+        impact_low,
+        bullish,
+        bearish,
+    };
+
+    EmbedStyleRepository::update(pool, guild_id, &update).await?;
+    let style = EmbedStyleRepository::get(pool, guild_id)
+        .await?
+        .unwrap_or_default();
+
+    let embed = CreateEmbed::new()
+        .title("Gaya Embed Diperbarui")
+        .field(
+            "Impact",
+            format!(
+                "Tinggi: {}\nSedang: {}\nRendah: {}",
+                style.impact_label(Some("high")),
+                style.impact_label(Some("medium")),
+                style.impact_label(Some("low"))
+            ),
+            true,
+        )
+        .field(
+            "Sentimen",
+            format!(
+                "Bullish: {}\nBearish: {}",
+                style.sentiment_marker(Some("bullish")).unwrap_or("-"),
+                style.sentiment_marker(Some("bearish")).unwrap_or("-")
+            ),
+            true,
+        )
+        .footer(CreateEmbedFooter::new(
+            "Gunakan /stocknews test untuk pratinjau",
+        ))
+        .color(0x00FF00);
+
+    let embed = branding::branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
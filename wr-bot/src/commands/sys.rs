@@ -1,3 +1,5 @@
+use crate::repository::connection;
+use crate::utils::branding::branded_embed;
 use crate::utils::sys::SysInfo;
 use poise::serenity_prelude as serenity;
 
@@ -11,15 +13,22 @@ pub async fn sys(ctx: Context<'_>) -> Result<(), Error> {
 
     ctx.defer_ephemeral().await?;
 
+    let started = std::time::Instant::now();
+    let database = match connection::ping(ctx.data().db.as_ref()).await {
+        Ok(()) => format!("OK ({} ms)", started.elapsed().as_millis()),
+        Err(e) => format!("Unreachable: {}", e),
+    };
 
     let embed = serenity::CreateEmbed::default()
         .title("Sys")
         .field("OS", &sistem.os, false)
         .field("CPU", &sistem.cpu, false)
         .field("Memory", &sistem.memory, false)
+        .field("Database", database, false)
         .color(serenity::Colour::BLUE)
         .timestamp(serenity::Timestamp::now());
 
+    let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
     ctx.send(
         poise::CreateReply::default()
             .embed(embed)
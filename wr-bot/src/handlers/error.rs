@@ -1,26 +1,117 @@
+use super::usage;
 use crate::commands::Data;
-use poise::serenity_prelude::CreateEmbed;
+use crate::repository::connection;
+use crate::utils::branding::branded_embed;
+use poise::serenity_prelude::{ChannelId, CreateEmbed, CreateMessage, Timestamp};
+use std::sync::OnceLock;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
+type Context<'a> = poise::Context<'a, Data, Error>;
+
+/// Embed descriptions are capped at 4096 characters; leave room for the code fence.
+const MAX_REPORT_LEN: usize = 3900;
+
+static ERROR_REPORT_CHANNEL: OnceLock<ChannelId> = OnceLock::new();
+
+/// Sets the channel that receives full reports of failed commands.
+pub fn init_error_report_channel(channel_id: ChannelId) {
+    let _ = ERROR_REPORT_CHANNEL.set(channel_id);
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((idx, _)) => format!("{}\n... (truncated)", &text[..idx]),
+        None => text.to_string(),
+    }
+}
+
+fn error_chain(error: &(dyn std::error::Error + 'static)) -> String {
+    let mut chain = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        chain.push_str(&format!("\nCaused by: {}", cause));
+        source = cause.source();
+    }
+    chain
+}
+
+/// Forwards a command failure to the error report channel, if configured.
+async fn report(ctx: Context<'_>, title: &str, details: &str) {
+    let Some(channel_id) = ERROR_REPORT_CHANNEL.get() else {
+        return;
+    };
+
+    let guild = ctx
+        .guild_id()
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| "DM".to_string());
+
+    let embed = CreateEmbed::new()
+        .title(format!("[ERROR] {}", title))
+        .description(format!("```\n{}\n```", truncate(details, MAX_REPORT_LEN)))
+        .field(
+            "Command",
+            format!("`{}`", ctx.command().qualified_name),
+            true,
+        )
+        .field(
+            "User",
+            format!("<@{}> ({})", ctx.author().id, ctx.author().id),
+            true,
+        )
+        .field("Guild", guild, true)
+        .field("Channel", format!("<#{}>", ctx.channel_id()), true)
+        .color(0xE74C3C)
+        .timestamp(Timestamp::now());
+
+    if let Err(e) = channel_id
+        .send_message(ctx.http(), CreateMessage::new().embed(embed))
+        .await
+    {
+        eprintln!("[WARN] Failed to send error report: {}", e);
+    }
+}
 
 /// Handle framework errors
 pub async fn on_error(error: poise::FrameworkError<'_, Data, Error>) {
     match error {
         poise::FrameworkError::Command { error, ctx, .. } => {
             eprintln!("Error in command '{}': {:?}", ctx.command().name, error);
+            usage::record(ctx, false).await;
+            if error
+                .downcast_ref::<sqlx::Error>()
+                .is_some_and(connection::is_unavailable)
+            {
+                let embed = CreateEmbed::new()
+                    .title("[ERROR] Database Unavailable")
+                    .description(
+                        "The database is not reachable right now. Please try again in a moment.",
+                    )
+                    .color(0xE74C3C);
+                // Not branded: loading the branding needs the database
+                let _ = ctx.send(poise::CreateReply::default().embed(embed)).await;
+                report(ctx, "Database Unavailable", &error_chain(error.as_ref())).await;
+                return;
+            }
             let embed = CreateEmbed::new()
                 .title("[ERROR] Command Failed")
                 .description(format!("{}", error))
                 .color(0xE74C3C);
+            let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
             let _ = ctx.send(poise::CreateReply::default().embed(embed)).await;
+            report(ctx, "Command Failed", &error_chain(error.as_ref())).await;
         }
         poise::FrameworkError::CommandPanic { payload, ctx, .. } => {
             eprintln!("Command '{}' panicked: {:?}", ctx.command().name, payload);
+            usage::record(ctx, false).await;
             let embed = CreateEmbed::new()
                 .title("[ERROR] Internal Error")
                 .description("An unexpected error occurred. Please try again later.")
                 .color(0xE74C3C);
+            let embed = branded_embed(&ctx.data().db, ctx.guild_id(), embed).await;
             let _ = ctx.send(poise::CreateReply::default().embed(embed)).await;
+            let payload = payload.unwrap_or_else(|| "no panic message".to_string());
+            report(ctx, "Command Panicked", &payload).await;
         }
         error => {
             eprintln!("Other error: {:?}", error);
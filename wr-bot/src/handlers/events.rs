@@ -56,11 +56,17 @@ async fn handle_voice_state_update(
     if old_channel.is_some() && old_channel != new_channel {
         if let Some(guild_id) = new.guild_id {
             if let Some(left_channel_id) = old_channel {
-                handle_auto_disconnect(ctx, data, guild_id, left_channel_id).await;
+                handle_listener_leave(ctx, data, guild_id, left_channel_id);
             }
         }
     }
 
+    if let (Some(guild_id), Some(joined_channel_id)) = (new.guild_id, new_channel)
+        && old_channel != new_channel
+    {
+        handle_listener_join(ctx, data, guild_id, joined_channel_id);
+    }
+
     if let Some(guild_id) = new.guild_id {
         handle_voice_logging(ctx, data, guild_id, old_channel, new_channel, new.user_id).await?;
     }
@@ -68,7 +74,8 @@ async fn handle_voice_state_update(
     Ok(())
 }
 
-async fn handle_auto_disconnect(
+/// Start the no-listener timer when the last user leaves the bot's voice channel.
+fn handle_listener_leave(
     ctx: &Context,
     data: &Data,
     guild_id: GuildId,
@@ -79,7 +86,7 @@ async fn handle_auto_disconnect(
         None => return,
     };
 
-    let should_disconnect = {
+    let channel_empty = {
         if let Some(guild) = ctx.cache.guild(guild_id) {
             let bot_in_channel = guild
                 .voice_states
@@ -107,25 +114,37 @@ async fn handle_auto_disconnect(
         }
     };
 
-    if should_disconnect {
-        println!("[MUSIC] No users in voice channel, auto-disconnecting...");
+    // The idle checker disconnects once the channel has stayed empty for the
+    // idle timeout, so someone rejoining in the meantime keeps the session
+    if channel_empty {
+        println!("[MUSIC] No users in voice channel, starting idle timer");
 
         if let Some(player) = &data.music_player {
-            if let Some(player_ctx) = player.get_player_context(guild_id) {
-                let _ = player_ctx.close();
-            }
-            player.clear_queue(guild_id);
+            player.set_alone(guild_id, true);
         }
+    }
+}
+
+/// Cancel the no-listener timer when someone joins the bot's voice channel.
+fn handle_listener_join(
+    ctx: &Context,
+    data: &Data,
+    guild_id: GuildId,
+    joined_channel_id: ChannelId,
+) {
+    let (Some(player), Some(bot_user_id)) = (&data.music_player, get_bot_user_id()) else {
+        return;
+    };
 
-        let _ = data.songbird.leave(guild_id).await;
+    let bot_channel = ctx.cache.guild(guild_id).and_then(|guild| {
+        guild
+            .voice_states
+            .get(&bot_user_id)
+            .and_then(|vs| vs.channel_id)
+    });
 
-        if let Some(player) = &data.music_player {
-            if let Some(channel_id) = player.get_text_channel(guild_id) {
-                let embed_msg = embed::info("Disconnect", "Left voice channel.");
-                let message = CreateMessage::new().embed(embed_msg);
-                let _ = channel_id.send_message(&ctx.http, message).await;
-            }
-        }
+    if bot_channel == Some(joined_channel_id) {
+        player.set_alone(guild_id, false);
     }
 }
 
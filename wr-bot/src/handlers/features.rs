@@ -0,0 +1,48 @@
+use crate::commands::Data;
+use crate::utils::features::{self, FeatureCategory};
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+type Context<'a> = poise::Context<'a, Data, Error>;
+
+/// Global command check that blocks commands whose category is disabled in
+/// the current guild. Subcommands inherit the category of their parent.
+pub async fn command_check(ctx: Context<'_>) -> Result<bool, Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(true);
+    };
+
+    let category = std::iter::once(ctx.command())
+        .chain(ctx.parent_commands().iter().rev().copied())
+        .find_map(|cmd| cmd.category.as_deref())
+        .and_then(FeatureCategory::from_category);
+    let Some(category) = category else {
+        return Ok(true);
+    };
+
+    require_feature(ctx, guild_id.get(), category).await
+}
+
+/// Replies and returns false when `category` is disabled in the guild.
+///
+/// For commands that serve several features, like `/setnewschannel`, and so
+/// cannot be gated by their own category.
+pub async fn require_feature(
+    ctx: Context<'_>,
+    guild_id: u64,
+    category: FeatureCategory,
+) -> Result<bool, Error> {
+    if features::for_guild(&ctx.data().db, guild_id)
+        .await
+        .is_enabled(category)
+    {
+        return Ok(true);
+    }
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content("This feature is disabled on this server.")
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(false)
+}
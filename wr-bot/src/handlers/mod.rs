@@ -1,7 +1,10 @@
 pub mod error;
 pub mod events;
+pub mod features;
 pub mod music;
+pub mod usage;
 
 pub use error::on_error;
 pub use events::handle_event;
+pub use features::command_check;
 pub use music::handle_track_end;
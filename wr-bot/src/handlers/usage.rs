@@ -0,0 +1,40 @@
+use crate::commands::Data;
+use crate::repository::UsageRepository;
+use std::time::Instant;
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+type Context<'a> = poise::Context<'a, Data, Error>;
+
+/// Invocation data set before a command runs, used to measure its latency.
+struct CommandStart(Instant);
+
+pub async fn pre_command(ctx: Context<'_>) {
+    ctx.set_invocation_data(CommandStart(Instant::now())).await;
+}
+
+pub async fn post_command(ctx: Context<'_>) {
+    record(ctx, true).await;
+}
+
+/// Stores one `command_usage` row on a background task.
+///
+/// The insert never delays the command and database failures are ignored,
+/// since analytics must not break anything users see.
+pub async fn record(ctx: Context<'_>, success: bool) {
+    let latency_ms = ctx
+        .invocation_data::<CommandStart>()
+        .await
+        .map(|start| start.0.elapsed().as_millis().min(i32::MAX as u128) as i32)
+        .unwrap_or(0);
+
+    let pool = ctx.data().db.clone();
+    let command_name = ctx.command().qualified_name.clone();
+    let guild_id = ctx.guild_id().map(|id| id.get());
+    let user_id = ctx.author().id.get();
+
+    tokio::spawn(async move {
+        let _ =
+            UsageRepository::record(&pool, &command_name, guild_id, user_id, success, latency_ms)
+                .await;
+    });
+}
@@ -9,6 +9,15 @@ pub struct CalendarChannel {
     pub mention_everyone: bool,
 }
 
+/// A calendar reminder that has already been announced.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SentCalendarEvent {
+    pub event_id: String,
+    pub event_title: String,
+    /// Unix seconds.
+    pub sent_at: i64,
+}
+
 pub struct CalendarRepository;
 
 impl CalendarRepository {
@@ -140,4 +149,24 @@ impl CalendarRepository {
 
         Ok(result.rows_affected())
     }
+
+    /// Most recently announced reminders, newest first.
+    pub async fn recent_events(
+        pool: &PgPool,
+        limit: i64,
+    ) -> Result<Vec<SentCalendarEvent>, sqlx::Error> {
+        let events = sqlx::query_as::<_, SentCalendarEvent>(
+            r#"
+            SELECT event_id, event_title, sent_at
+            FROM calendar_events_sent
+            ORDER BY sent_at DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(events)
+    }
 }
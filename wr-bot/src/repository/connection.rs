@@ -1,16 +1,130 @@
 use sqlx::{PgPool, postgres::PgPoolOptions};
+use std::env;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub type DbPool = Arc<PgPool>;
 
-pub async fn create_pool(database_url: &str) -> Result<DbPool, sqlx::Error> {
+#[derive(Debug, Clone, Copy)]
+pub struct PoolSettings {
+    pub max_connections: u32,
+    pub acquire_timeout: Duration,
+    pub idle_timeout: Duration,
+    /// How many times startup tries to connect before giving up.
+    pub connect_attempts: u32,
+}
+
+impl Default for PoolSettings {
+    fn default() -> Self {
+        Self {
+            max_connections: 10,
+            acquire_timeout: Duration::from_secs(5),
+            idle_timeout: Duration::from_secs(600),
+            connect_attempts: 10,
+        }
+    }
+}
+
+impl PoolSettings {
+    /// Reads `DB_MAX_CONNECTIONS`, `DB_ACQUIRE_TIMEOUT_SECS`,
+    /// `DB_IDLE_TIMEOUT_SECS` and `DB_CONNECT_ATTEMPTS`, falling back to the defaults when unset or invalid.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        let secs = |key: &str, default: Duration| {
+            env::var(key)
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(default)
+        };
+
+        Self {
+            max_connections: env::var("DB_MAX_CONNECTIONS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|n| *n > 0)
+                .unwrap_or(defaults.max_connections),
+            acquire_timeout: secs("DB_ACQUIRE_TIMEOUT_SECS", defaults.acquire_timeout),
+            idle_timeout: secs("DB_IDLE_TIMEOUT_SECS", defaults.idle_timeout),
+            connect_attempts: env::var("DB_CONNECT_ATTEMPTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|n| *n > 0)
+                .unwrap_or(defaults.connect_attempts),
+        }
+    }
+}
+
+pub async fn create_pool(
+    database_url: &str,
+    settings: PoolSettings,
+) -> Result<DbPool, sqlx::Error> {
     let pool = PgPoolOptions::new()
-        .max_connections(10)
+        .max_connections(settings.max_connections)
+        .acquire_timeout(settings.acquire_timeout)
+        .idle_timeout(settings.idle_timeout)
         .connect(database_url)
         .await?;
 
     sqlx::migrate!("./migrations").run(&pool).await?;
 
-    println!("[OK] Database connected and migrations applied");
+    println!(
+        "[OK] Database connected and migrations applied (max {} connections, acquire timeout {}s, idle timeout {}s)",
+        settings.max_connections,
+        settings.acquire_timeout.as_secs(),
+        settings.idle_timeout.as_secs()
+    );
     Ok(Arc::new(pool))
 }
+
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Like [`create_pool`], but retries with exponential backoff so the bot can
+/// start before the database is ready.
+///
+/// Only errors from [`is_unavailable`] are retried; anything else, like a
+/// failed migration, would fail the same way again and is returned at once.
+pub async fn create_pool_with_retry(
+    database_url: &str,
+    settings: PoolSettings,
+) -> Result<DbPool, sqlx::Error> {
+    let mut delay = Duration::from_secs(1);
+    let mut attempt = 1;
+
+    loop {
+        match create_pool(database_url, settings).await {
+            Ok(pool) => return Ok(pool),
+            Err(e) if attempt < settings.connect_attempts && is_unavailable(&e) => {
+                eprintln!(
+                    "[WARN] Database connection attempt {}/{} failed: {}. Retrying in {}s",
+                    attempt,
+                    settings.connect_attempts,
+                    e,
+                    delay.as_secs()
+                );
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(MAX_RETRY_DELAY);
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Whether `error` means the database could not be reached, as opposed to a
+/// problem with the query itself.
+pub fn is_unavailable(error: &sqlx::Error) -> bool {
+    match error {
+        sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::Io(_) => true,
+        // cannot_connect_now: the server is still starting up or shutting down
+        sqlx::Error::Database(e) => e.code().as_deref() == Some("57P03"),
+        _ => false,
+    }
+}
+
+/// Round-trips a trivial query to check the database is reachable.
+pub async fn ping(pool: &PgPool) -> Result<(), sqlx::Error> {
+    sqlx::query("SELECT 1").execute(pool).await?;
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+/// Custom markers for stock news embeds. Unset fields use the defaults.
+#[derive(Debug, Clone, Default, sqlx::FromRow)]
+pub struct StockEmbedStyle {
+    pub impact_high: Option<String>,
+    pub impact_medium: Option<String>,
+    pub impact_low: Option<String>,
+    pub bullish: Option<String>,
+    pub bearish: Option<String>,
+}
+
+impl StockEmbedStyle {
+    /// Label for an impact level, `-` when the level is unknown.
+    pub fn impact_label(&self, impact_level: Option<&str>) -> &str {
+        match impact_level {
+            Some("high") => self.impact_high.as_deref().unwrap_or("HIGH"),
+            Some("medium") => self.impact_medium.as_deref().unwrap_or("MED"),
+            Some("low") => self.impact_low.as_deref().unwrap_or("LOW"),
+            _ => "-",
+        }
+    }
+
+    /// Configured marker for a sentiment, if the guild set one.
+    pub fn sentiment_marker(&self, sentiment: Option<&str>) -> Option<&str> {
+        match sentiment {
+            Some("bullish") => self.bullish.as_deref(),
+            Some("bearish") => self.bearish.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+pub struct EmbedStyleRepository;
+
+impl EmbedStyleRepository {
+    pub async fn get(pool: &PgPool, guild_id: u64) -> Result<Option<StockEmbedStyle>, sqlx::Error> {
+        let style = sqlx::query_as::<_, StockEmbedStyle>(
+            r#"
+            SELECT impact_high, impact_medium, impact_low, bullish, bearish
+            FROM guild_embed_style
+            WHERE guild_id = $1
+            "#,
+        )
+        .bind(guild_id as i64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(style)
+    }
+
+    /// Styles for every guild in `guild_ids` that has one, keyed by guild id.
+    pub async fn get_for_guilds(
+        pool: &PgPool,
+        guild_ids: &[i64],
+    ) -> Result<HashMap<i64, StockEmbedStyle>, sqlx::Error> {
+        #[derive(sqlx::FromRow)]
+        struct Row {
+            guild_id: i64,
+            #[sqlx(flatten)]
+            style: StockEmbedStyle,
+        }
+
+        let rows = sqlx::query_as::<_, Row>(
+            r#"
+            SELECT guild_id, impact_high, impact_medium, impact_low, bullish, bearish
+            FROM guild_embed_style
+            WHERE guild_id = ANY($1)
+            "#,
+        )
+        .bind(guild_ids)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| (r.guild_id, r.style)).collect())
+    }
+
+    /// Stores the fields that are set in `style`, keeping the others as they were.
+    pub async fn update(
+        pool: &PgPool,
+        guild_id: u64,
+        style: &StockEmbedStyle,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_embed_style
+                (guild_id, impact_high, impact_medium, impact_low, bullish, bearish, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, NOW())
+            ON CONFLICT(guild_id) DO UPDATE SET
+                impact_high = COALESCE(EXCLUDED.impact_high, guild_embed_style.impact_high),
+                impact_medium = COALESCE(EXCLUDED.impact_medium, guild_embed_style.impact_medium),
+                impact_low = COALESCE(EXCLUDED.impact_low, guild_embed_style.impact_low),
+                bullish = COALESCE(EXCLUDED.bullish, guild_embed_style.bullish),
+                bearish = COALESCE(EXCLUDED.bearish, guild_embed_style.bearish),
+                updated_at = NOW()
+            "#,
+        )
+        .bind(guild_id as i64)
+        .bind(&style.impact_high)
+        .bind(&style.impact_medium)
+        .bind(&style.impact_low)
+        .bind(&style.bullish)
+        .bind(&style.bearish)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn reset(pool: &PgPool, guild_id: u64) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM guild_embed_style WHERE guild_id = $1")
+            .bind(guild_id as i64)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+}
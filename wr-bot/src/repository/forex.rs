@@ -1,3 +1,5 @@
+use crate::repository::news::{SentSource, sent_key};
+use crate::utils::sql::contains_pattern;
 use sqlx::PgPool;
 
 #[derive(Debug, Clone, sqlx::FromRow)]
@@ -6,6 +8,28 @@ pub struct ForexChannel {
     pub channel_id: i64,
     pub guild_id: i64,
     pub is_active: bool,
+    pub webhook_url: Option<String>,
+    pub digest: bool,
+    pub digest_interval_minutes: i32,
+    pub mention_role_id: Option<i64>,
+    pub min_sentiment_confidence: Option<f64>,
+    pub use_thread: bool,
+    pub thread_id: Option<i64>,
+    /// `None` follows the `mention_everyone` flag sent by the news server.
+    pub mention_everyone: Option<bool>,
+}
+
+/// Processed forex article as stored by the news-server
+/// (`news_articles` joined with its analysis and source).
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ForexNews {
+    pub title: String,
+    pub original_url: String,
+    pub source_name: Option<String>,
+    pub summary: Option<String>,
+    pub sentiment: Option<String>,
+    pub impact_level: Option<String>,
+    pub published_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 pub struct ForexRepository;
@@ -31,6 +55,30 @@ impl ForexRepository {
         Ok(())
     }
 
+    /// Points the guild's forex news at `channel_id` and (re)activates it.
+    pub async fn subscribe(
+        pool: &PgPool,
+        guild_id: u64,
+        channel_id: u64,
+        mention_everyone: Option<bool>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO forex_channels (guild_id, channel_id, is_active, mention_everyone)
+            VALUES ($1, $2, TRUE, $3)
+            ON CONFLICT(guild_id) DO UPDATE
+            SET channel_id = $2, is_active = TRUE, mention_everyone = $3
+            "#,
+        )
+        .bind(guild_id as i64)
+        .bind(channel_id as i64)
+        .bind(mention_everyone)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn disable_channel(pool: &PgPool, guild_id: u64) -> Result<(), sqlx::Error> {
         sqlx::query!(
             "UPDATE forex_channels SET is_active = FALSE WHERE guild_id = $1",
@@ -54,9 +102,10 @@ impl ForexRepository {
     }
 
     pub async fn get_active_channels(pool: &PgPool) -> Result<Vec<ForexChannel>, sqlx::Error> {
-        let channels = sqlx::query_as!(
-            ForexChannel,
-            "SELECT id, channel_id, guild_id, is_active FROM forex_channels WHERE is_active = TRUE"
+        let channels = sqlx::query_as::<_, ForexChannel>(
+            r#"SELECT id, channel_id, guild_id, is_active, webhook_url, digest, digest_interval_minutes,
+                      mention_role_id, min_sentiment_confidence, use_thread, thread_id, mention_everyone
+               FROM forex_channels WHERE is_active = TRUE"#,
         )
         .fetch_all(pool)
         .await?;
@@ -68,22 +117,148 @@ impl ForexRepository {
         pool: &PgPool,
         guild_id: u64,
     ) -> Result<Option<ForexChannel>, sqlx::Error> {
-        let channel = sqlx::query_as!(
-            ForexChannel,
-            "SELECT id, channel_id, guild_id, is_active FROM forex_channels WHERE guild_id = $1",
-            guild_id as i64,
+        let channel = sqlx::query_as::<_, ForexChannel>(
+            r#"SELECT id, channel_id, guild_id, is_active, webhook_url, digest, digest_interval_minutes,
+                      mention_role_id, min_sentiment_confidence, use_thread, thread_id, mention_everyone
+               FROM forex_channels WHERE guild_id = $1"#,
         )
+        .bind(guild_id as i64)
         .fetch_optional(pool)
         .await?;
 
         Ok(channel)
     }
 
+    /// Every forex subscription in `guild_id`, active or not.
+    pub async fn get_channels_for_guild(
+        pool: &PgPool,
+        guild_id: u64,
+    ) -> Result<Vec<ForexChannel>, sqlx::Error> {
+        let channels = sqlx::query_as::<_, ForexChannel>(
+            r#"SELECT id, channel_id, guild_id, is_active, webhook_url, digest, digest_interval_minutes,
+                      mention_role_id, min_sentiment_confidence, use_thread, thread_id, mention_everyone
+               FROM forex_channels WHERE guild_id = $1
+               ORDER BY id"#,
+        )
+        .bind(guild_id as i64)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(channels)
+    }
+
+    pub async fn set_webhook(
+        pool: &PgPool,
+        guild_id: u64,
+        webhook_url: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("UPDATE forex_channels SET webhook_url = $2 WHERE guild_id = $1")
+            .bind(guild_id as i64)
+            .bind(webhook_url)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn clear_webhook_for_channel(
+        pool: &PgPool,
+        channel_id: u64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE forex_channels SET webhook_url = NULL WHERE channel_id = $1")
+            .bind(channel_id as i64)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_digest(
+        pool: &PgPool,
+        guild_id: u64,
+        enabled: bool,
+        interval_minutes: i32,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE forex_channels SET digest = $2, digest_interval_minutes = $3 WHERE guild_id = $1",
+        )
+        .bind(guild_id as i64)
+        .bind(enabled)
+        .bind(interval_minutes)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn set_mention_role(
+        pool: &PgPool,
+        guild_id: u64,
+        role_id: Option<u64>,
+    ) -> Result<bool, sqlx::Error> {
+        let result =
+            sqlx::query("UPDATE forex_channels SET mention_role_id = $2 WHERE guild_id = $1")
+                .bind(guild_id as i64)
+                .bind(role_id.map(|id| id as i64))
+                .execute(pool)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn set_min_confidence(
+        pool: &PgPool,
+        guild_id: u64,
+        min_confidence: Option<f64>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE forex_channels SET min_sentiment_confidence = $2 WHERE guild_id = $1",
+        )
+        .bind(guild_id as i64)
+        .bind(min_confidence)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Turns thread delivery on or off; returns false when the guild has no
+    /// forex channel.
+    pub async fn set_use_thread(
+        pool: &PgPool,
+        guild_id: u64,
+        enabled: bool,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("UPDATE forex_channels SET use_thread = $2 WHERE guild_id = $1")
+            .bind(guild_id as i64)
+            .bind(enabled)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn set_thread(
+        pool: &PgPool,
+        guild_id: u64,
+        thread_id: Option<u64>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE forex_channels SET thread_id = $2 WHERE guild_id = $1")
+            .bind(guild_id as i64)
+            .bind(thread_id.map(|id| id as i64))
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn is_news_sent(pool: &PgPool, news_id: &str) -> Result<bool, sqlx::Error> {
-        let count = sqlx::query_scalar!(
-            r#"SELECT COUNT(*) as "count!" FROM forex_news_sent WHERE news_id = $1"#,
-            news_id,
+        let (news_id, source_type) = sent_key(SentSource::Forex, news_id);
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM forex_news_sent WHERE news_id = $1 AND source_type = $2",
         )
+        .bind(news_id)
+        .bind(source_type)
         .fetch_one(pool)
         .await?;
 
@@ -95,17 +270,19 @@ impl ForexRepository {
         news_id: &str,
         source: &str,
     ) -> Result<(), sqlx::Error> {
+        let (news_id, source_type) = sent_key(SentSource::Forex, news_id);
         let now = chrono::Utc::now().timestamp();
-        sqlx::query!(
+        sqlx::query(
             r#"
-            INSERT INTO forex_news_sent (news_id, source, sent_at)
-            VALUES ($1, $2, $3)
-            ON CONFLICT(news_id) DO NOTHING
+            INSERT INTO forex_news_sent (news_id, source, sent_at, source_type)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT(news_id, source_type) DO NOTHING
             "#,
-            news_id,
-            source,
-            now,
         )
+        .bind(news_id)
+        .bind(source)
+        .bind(now)
+        .bind(source_type)
         .execute(pool)
         .await?;
 
@@ -114,10 +291,95 @@ impl ForexRepository {
 
     pub async fn cleanup_old_news(pool: &PgPool, days: i64) -> Result<u64, sqlx::Error> {
         let cutoff = chrono::Utc::now().timestamp() - (days * 86400);
-        let result = sqlx::query!("DELETE FROM forex_news_sent WHERE sent_at < $1", cutoff,)
-            .execute(pool)
-            .await?;
+        let result =
+            sqlx::query("DELETE FROM forex_news_sent WHERE sent_at < $1 AND source_type = $2")
+                .bind(cutoff)
+                .bind(SentSource::Forex.as_str())
+                .execute(pool)
+                .await?;
 
         Ok(result.rows_affected())
     }
+
+    /// Latest processed forex articles, optionally limited to those whose
+    /// analysis mentions `currency`.
+    pub async fn get_recent_news(
+        pool: &PgPool,
+        currency: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<ForexNews>, sqlx::Error> {
+        let news = sqlx::query_as::<_, ForexNews>(
+            r#"
+            SELECT a.original_title AS title, a.original_url, s.name AS source_name,
+                   a.summary, n.sentiment, n.impact_level, a.published_at
+            FROM news_articles a
+            LEFT JOIN news_analyses n ON n.article_id = a.id
+            LEFT JOIN news_sources s ON s.id = a.source_id
+            WHERE a.is_processed = TRUE
+              AND ($1::TEXT IS NULL OR $1 = ANY(n.currencies))
+            ORDER BY a.published_at DESC NULLS LAST
+            LIMIT $2
+            "#,
+        )
+        .bind(currency)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(news)
+    }
+
+    /// Processed forex articles whose title or summary contains `keyword`,
+    /// newest first. Wildcards in `keyword` are matched literally.
+    pub async fn search_news(
+        pool: &PgPool,
+        keyword: &str,
+        limit: i64,
+    ) -> Result<Vec<ForexNews>, sqlx::Error> {
+        let news = sqlx::query_as::<_, ForexNews>(
+            r#"
+            SELECT a.original_title AS title, a.original_url, s.name AS source_name,
+                   a.summary, n.sentiment, n.impact_level, a.published_at
+            FROM news_articles a
+            LEFT JOIN news_analyses n ON n.article_id = a.id
+            LEFT JOIN news_sources s ON s.id = a.source_id
+            WHERE a.is_processed = TRUE
+              AND (a.original_title ILIKE $1 ESCAPE '\' OR a.summary ILIKE $1 ESCAPE '\')
+            ORDER BY a.published_at DESC NULLS LAST
+            LIMIT $2
+            "#,
+        )
+        .bind(contains_pattern(keyword))
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(news)
+    }
+
+    /// Sentiment distribution of forex articles mentioning `currency`
+    /// published within the last `hours` hours.
+    pub async fn get_sentiment_counts(
+        pool: &PgPool,
+        currency: &str,
+        hours: i32,
+    ) -> Result<Vec<(Option<String>, i64)>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, (Option<String>, i64)>(
+            r#"
+            SELECT n.sentiment, COUNT(*) AS count
+            FROM news_articles a
+            JOIN news_analyses n ON n.article_id = a.id
+            WHERE a.is_processed = TRUE
+              AND $1 = ANY(n.currencies)
+              AND a.published_at > NOW() - make_interval(hours => $2)
+            GROUP BY n.sentiment
+            "#,
+        )
+        .bind(currency)
+        .bind(hours)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
 }
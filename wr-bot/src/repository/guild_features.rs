@@ -0,0 +1,78 @@
+use crate::utils::features::FeatureCategory;
+use sqlx::PgPool;
+
+#[derive(Debug, Clone, Copy, sqlx::FromRow)]
+pub struct GuildFeatures {
+    pub forex: bool,
+    pub stock: bool,
+    pub calendar: bool,
+    pub music: bool,
+    pub ai: bool,
+    pub moderation: bool,
+}
+
+impl Default for GuildFeatures {
+    fn default() -> Self {
+        Self {
+            forex: true,
+            stock: true,
+            calendar: true,
+            music: true,
+            ai: true,
+            moderation: true,
+        }
+    }
+}
+
+impl GuildFeatures {
+    pub fn is_enabled(&self, category: FeatureCategory) -> bool {
+        match category {
+            FeatureCategory::Forex => self.forex,
+            FeatureCategory::Stock => self.stock,
+            FeatureCategory::Calendar => self.calendar,
+            FeatureCategory::Music => self.music,
+            FeatureCategory::Ai => self.ai,
+            FeatureCategory::Moderation => self.moderation,
+        }
+    }
+}
+
+pub struct GuildFeaturesRepository;
+
+impl GuildFeaturesRepository {
+    pub async fn get(pool: &PgPool, guild_id: u64) -> Result<Option<GuildFeatures>, sqlx::Error> {
+        let features = sqlx::query_as::<_, GuildFeatures>(
+            "SELECT forex, stock, calendar, music, ai, moderation FROM guild_features WHERE guild_id = $1",
+        )
+        .bind(guild_id as i64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(features)
+    }
+
+    pub async fn set(
+        pool: &PgPool,
+        guild_id: u64,
+        category: FeatureCategory,
+        enabled: bool,
+    ) -> Result<(), sqlx::Error> {
+        // The column name comes from a fixed enum, never from user input.
+        let column = category.column();
+        let query = format!(
+            r#"
+            INSERT INTO guild_features (guild_id, {column}, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT(guild_id) DO UPDATE SET {column} = EXCLUDED.{column}, updated_at = NOW()
+            "#
+        );
+
+        sqlx::query(&query)
+            .bind(guild_id as i64)
+            .bind(enabled)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+}
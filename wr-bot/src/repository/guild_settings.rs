@@ -0,0 +1,160 @@
+use sqlx::PgPool;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct GuildSettings {
+    pub guild_id: i64,
+    pub locale: String,
+    pub dj_role_id: Option<i64>,
+    pub dj_alone_bypass: bool,
+}
+
+/// Embed branding for a guild; unset fields keep the bot's defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, sqlx::FromRow)]
+pub struct GuildBranding {
+    pub brand_footer: Option<String>,
+    pub brand_color: Option<i32>,
+    pub brand_thumbnail_url: Option<String>,
+}
+
+pub struct GuildSettingsRepository;
+
+impl GuildSettingsRepository {
+    pub async fn get(pool: &PgPool, guild_id: u64) -> Result<Option<GuildSettings>, sqlx::Error> {
+        let settings = sqlx::query_as::<_, GuildSettings>(
+            "SELECT guild_id, locale, dj_role_id, dj_alone_bypass FROM guild_settings WHERE guild_id = $1",
+        )
+        .bind(guild_id as i64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(settings)
+    }
+
+    /// Locale code stored for `guild_id`, if one was set.
+    pub async fn get_locale(pool: &PgPool, guild_id: u64) -> Result<Option<String>, sqlx::Error> {
+        let locale = sqlx::query_scalar::<_, String>(
+            "SELECT locale FROM guild_settings WHERE guild_id = $1",
+        )
+        .bind(guild_id as i64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(locale)
+    }
+
+    pub async fn set_locale(pool: &PgPool, guild_id: u64, locale: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_settings (guild_id, locale, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT(guild_id) DO UPDATE SET locale = EXCLUDED.locale, updated_at = NOW()
+            "#,
+        )
+        .bind(guild_id as i64)
+        .bind(locale)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_dj_role(
+        pool: &PgPool,
+        guild_id: u64,
+        role_id: Option<u64>,
+        alone_bypass: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_settings (guild_id, dj_role_id, dj_alone_bypass, updated_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT(guild_id) DO UPDATE
+            SET dj_role_id = EXCLUDED.dj_role_id, dj_alone_bypass = EXCLUDED.dj_alone_bypass, updated_at = NOW()
+            "#,
+        )
+        .bind(guild_id as i64)
+        .bind(role_id.map(|id| id as i64))
+        .bind(alone_bypass)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Chart analysis persona template stored for `guild_id`, if one was set.
+    pub async fn get_analysis_prompt(
+        pool: &PgPool,
+        guild_id: u64,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let template = sqlx::query_scalar::<_, Option<String>>(
+            "SELECT ai_analysis_prompt_template FROM guild_settings WHERE guild_id = $1",
+        )
+        .bind(guild_id as i64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(template.flatten())
+    }
+
+    /// Stores the analysis persona template; `None` restores the default.
+    pub async fn set_analysis_prompt(
+        pool: &PgPool,
+        guild_id: u64,
+        template: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_settings (guild_id, ai_analysis_prompt_template, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT(guild_id) DO UPDATE
+            SET ai_analysis_prompt_template = EXCLUDED.ai_analysis_prompt_template, updated_at = NOW()
+            "#,
+        )
+        .bind(guild_id as i64)
+        .bind(template)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_branding(
+        pool: &PgPool,
+        guild_id: u64,
+    ) -> Result<Option<GuildBranding>, sqlx::Error> {
+        let branding = sqlx::query_as::<_, GuildBranding>(
+            "SELECT brand_footer, brand_color, brand_thumbnail_url FROM guild_settings WHERE guild_id = $1",
+        )
+        .bind(guild_id as i64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(branding)
+    }
+
+    pub async fn set_branding(
+        pool: &PgPool,
+        guild_id: u64,
+        branding: &GuildBranding,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_settings (guild_id, brand_footer, brand_color, brand_thumbnail_url, updated_at)
+            VALUES ($1, $2, $3, $4, NOW())
+            ON CONFLICT(guild_id) DO UPDATE
+            SET brand_footer = EXCLUDED.brand_footer,
+                brand_color = EXCLUDED.brand_color,
+                brand_thumbnail_url = EXCLUDED.brand_thumbnail_url,
+                updated_at = NOW()
+            "#,
+        )
+        .bind(guild_id as i64)
+        .bind(&branding.brand_footer)
+        .bind(branding.brand_color)
+        .bind(&branding.brand_thumbnail_url)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
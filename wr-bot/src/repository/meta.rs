@@ -0,0 +1,30 @@
+use sqlx::PgPool;
+
+pub struct MetaRepository;
+
+impl MetaRepository {
+    pub async fn get(pool: &PgPool, key: &str) -> Result<Option<String>, sqlx::Error> {
+        let value = sqlx::query_scalar::<_, String>("SELECT value FROM bot_meta WHERE key = $1")
+            .bind(key)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(value)
+    }
+
+    pub async fn set(pool: &PgPool, key: &str, value: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO bot_meta (key, value, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT(key) DO UPDATE SET value = EXCLUDED.value, updated_at = NOW()
+            "#,
+        )
+        .bind(key)
+        .bind(value)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -1,11 +1,27 @@
 pub mod calendar;
 pub mod connection;
+pub mod embed_style;
 pub mod forex;
+pub mod guild_features;
+pub mod guild_settings;
+pub mod meta;
 pub mod moderation;
+pub mod mute;
+pub mod news;
 pub mod stock;
+pub mod symbol_policy;
+pub mod usage;
 
-pub use calendar::{CalendarChannel, CalendarRepository};
-pub use connection::{DbPool, create_pool};
-pub use forex::{ForexChannel, ForexRepository};
+pub use calendar::{CalendarChannel, CalendarRepository, SentCalendarEvent};
+pub use connection::{DbPool, PoolSettings, create_pool, create_pool_with_retry};
+pub use embed_style::{EmbedStyleRepository, StockEmbedStyle};
+pub use forex::{ForexChannel, ForexNews, ForexRepository};
+pub use guild_features::{GuildFeatures, GuildFeaturesRepository};
+pub use guild_settings::{GuildBranding, GuildSettings, GuildSettingsRepository};
+pub use meta::MetaRepository;
 pub use moderation::{ModConfig, ModerationRepository, Warning};
+pub use mute::{Mute, MuteRepository};
+pub use news::{NewsRepository, TopNews};
 pub use stock::{StockChannel, StockRepository};
+pub use symbol_policy::{SymbolPolicyEntry, SymbolPolicyRepository};
+pub use usage::{CommandStat, UsageRepository};
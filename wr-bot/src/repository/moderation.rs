@@ -16,6 +16,11 @@ pub struct ModConfig {
     pub guild_id: i64,
     pub auto_role_id: Option<i64>,
     pub log_channel_id: Option<i64>,
+    /// Warnings after which `/warn` escalates to a timeout
+    pub warn_limit: Option<i32>,
+    /// Length of the escalation timeout
+    pub timeout_minutes: Option<i32>,
+    pub mute_role_id: Option<i64>,
 }
 
 pub struct ModerationRepository;
@@ -123,17 +128,30 @@ impl ModerationRepository {
         pool: &PgPool,
         guild_id: u64,
     ) -> Result<Option<ModConfig>, sqlx::Error> {
-        let config = sqlx::query_as!(
-            ModConfig,
-            "SELECT guild_id, auto_role_id, log_channel_id FROM mod_config WHERE guild_id = $1",
-            guild_id as i64,
+        let config = sqlx::query_as::<_, ModConfig>(
+            r#"SELECT guild_id, auto_role_id, log_channel_id, warn_limit, timeout_minutes, mute_role_id
+               FROM mod_config WHERE guild_id = $1"#,
         )
+        .bind(guild_id as i64)
         .fetch_optional(pool)
         .await?;
 
         Ok(config)
     }
 
+    /// Log channel for a guild, if one is configured
+    pub async fn log_channel(pool: &PgPool, guild_id: u64) -> Result<Option<u64>, sqlx::Error> {
+        let channel_id = sqlx::query_scalar::<_, Option<i64>>(
+            "SELECT log_channel_id FROM mod_config WHERE guild_id = $1",
+        )
+        .bind(guild_id as i64)
+        .fetch_optional(pool)
+        .await?
+        .flatten();
+
+        Ok(channel_id.map(|id| id as u64))
+    }
+
     /// Set auto-role for a guild
     pub async fn set_auto_role(
         pool: &PgPool,
@@ -199,4 +217,67 @@ impl ModerationRepository {
 
         Ok(())
     }
+
+    /// Set or clear the warning count that triggers an automatic timeout
+    pub async fn set_warn_limit(
+        pool: &PgPool,
+        guild_id: u64,
+        limit: Option<i32>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO mod_config (guild_id, warn_limit)
+            VALUES ($1, $2)
+            ON CONFLICT(guild_id) DO UPDATE SET warn_limit = EXCLUDED.warn_limit
+            "#,
+        )
+        .bind(guild_id as i64)
+        .bind(limit)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Set or clear the escalation timeout length
+    pub async fn set_timeout_minutes(
+        pool: &PgPool,
+        guild_id: u64,
+        minutes: Option<i32>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO mod_config (guild_id, timeout_minutes)
+            VALUES ($1, $2)
+            ON CONFLICT(guild_id) DO UPDATE SET timeout_minutes = EXCLUDED.timeout_minutes
+            "#,
+        )
+        .bind(guild_id as i64)
+        .bind(minutes)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Set or clear the mute role for a guild
+    pub async fn set_mute_role(
+        pool: &PgPool,
+        guild_id: u64,
+        role_id: Option<u64>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO mod_config (guild_id, mute_role_id)
+            VALUES ($1, $2)
+            ON CONFLICT(guild_id) DO UPDATE SET mute_role_id = EXCLUDED.mute_role_id
+            "#,
+        )
+        .bind(guild_id as i64)
+        .bind(role_id.map(|id| id as i64))
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
 }
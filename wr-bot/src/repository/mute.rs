@@ -0,0 +1,151 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Mute {
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub role_id: i64,
+    pub unmute_at: DateTime<Utc>,
+}
+
+pub struct MuteRepository;
+
+impl MuteRepository {
+    /// Record a role mute, replacing any earlier one for the same member
+    pub async fn schedule(
+        pool: &PgPool,
+        guild_id: u64,
+        user_id: u64,
+        role_id: u64,
+        unmute_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO mutes (guild_id, user_id, role_id, unmute_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT(guild_id, user_id) DO UPDATE
+            SET role_id = EXCLUDED.role_id, unmute_at = EXCLUDED.unmute_at, created_at = NOW()
+            "#,
+        )
+        .bind(guild_id as i64)
+        .bind(user_id as i64)
+        .bind(role_id as i64)
+        .bind(unmute_at)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Drop a member's mute, returning the muted role if there was one
+    pub async fn cancel(
+        pool: &PgPool,
+        guild_id: u64,
+        user_id: u64,
+    ) -> Result<Option<u64>, sqlx::Error> {
+        let role_id = sqlx::query_scalar::<_, i64>(
+            "DELETE FROM mutes WHERE guild_id = $1 AND user_id = $2 RETURNING role_id",
+        )
+        .bind(guild_id as i64)
+        .bind(user_id as i64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(role_id.map(|id| id as u64))
+    }
+
+    /// A member's current mute, if any
+    pub async fn get(
+        pool: &PgPool,
+        guild_id: u64,
+        user_id: u64,
+    ) -> Result<Option<Mute>, sqlx::Error> {
+        let mute = sqlx::query_as::<_, Mute>(
+            r#"
+            SELECT guild_id, user_id, role_id, unmute_at
+            FROM mutes
+            WHERE guild_id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(guild_id as i64)
+        .bind(user_id as i64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(mute)
+    }
+
+    /// Drop a lifted mute, unless it was extended to a new `unmute_at` since
+    pub async fn clear_lifted(
+        pool: &PgPool,
+        guild_id: u64,
+        user_id: u64,
+        unmute_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM mutes WHERE guild_id = $1 AND user_id = $2 AND unmute_at = $3")
+            .bind(guild_id as i64)
+            .bind(user_id as i64)
+            .bind(unmute_at)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Mutes that end at or before `ts`
+    pub async fn due_before(pool: &PgPool, ts: DateTime<Utc>) -> Result<Vec<Mute>, sqlx::Error> {
+        let mutes = sqlx::query_as::<_, Mute>(
+            r#"
+            SELECT guild_id, user_id, role_id, unmute_at
+            FROM mutes
+            WHERE unmute_at <= $1
+            ORDER BY unmute_at
+            "#,
+        )
+        .bind(ts)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(mutes)
+    }
+
+    /// All mutes that have not been lifted yet
+    pub async fn pending(pool: &PgPool) -> Result<Vec<Mute>, sqlx::Error> {
+        let mutes = sqlx::query_as::<_, Mute>(
+            "SELECT guild_id, user_id, role_id, unmute_at FROM mutes ORDER BY unmute_at",
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(mutes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test]
+    #[ignore = "needs DATABASE_URL pointing at a Postgres server"]
+    async fn extended_mute_survives_clearing_the_old_one(pool: PgPool) {
+        let old = Utc::now() - chrono::Duration::minutes(1);
+        MuteRepository::schedule(&pool, 1, 2, 3, old).await.unwrap();
+        let stored = MuteRepository::get(&pool, 1, 2).await.unwrap().unwrap();
+
+        let extended = Utc::now() + chrono::Duration::hours(1);
+        MuteRepository::schedule(&pool, 1, 2, 3, extended)
+            .await
+            .unwrap();
+        MuteRepository::clear_lifted(&pool, 1, 2, stored.unmute_at)
+            .await
+            .unwrap();
+        assert!(MuteRepository::get(&pool, 1, 2).await.unwrap().is_some());
+
+        let stored = MuteRepository::get(&pool, 1, 2).await.unwrap().unwrap();
+        MuteRepository::clear_lifted(&pool, 1, 2, stored.unmute_at)
+            .await
+            .unwrap();
+        assert!(MuteRepository::get(&pool, 1, 2).await.unwrap().is_none());
+    }
+}
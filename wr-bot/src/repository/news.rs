@@ -0,0 +1,107 @@
+use sqlx::PgPool;
+
+/// A headline from either news stream, tagged with where it came from.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct TopNews {
+    /// `forex` or `stock`.
+    pub origin: String,
+    pub title: String,
+    pub original_url: String,
+    pub source_name: Option<String>,
+    pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Feed a `forex_news_sent` row belongs to.
+///
+/// Forex and stock ids come from separate sequences on the news server, so a
+/// sent article is only unique together with its source type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SentSource {
+    Forex,
+    Stock,
+}
+
+impl SentSource {
+    /// Value of the `source_type` column.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SentSource::Forex => "forex",
+            SentSource::Stock => "stock",
+        }
+    }
+}
+
+/// Dedup key `(news_id, source_type)` for a sent article.
+///
+/// Ids are stored as received. Stock ids used to be told apart by a `stock_`
+/// prefix instead, which the source type replaces.
+pub fn sent_key(source: SentSource, news_id: &str) -> (&str, &'static str) {
+    (news_id, source.as_str())
+}
+
+/// Queries spanning both the forex and the stock news tables.
+pub struct NewsRepository;
+
+impl NewsRepository {
+    /// High impact forex and stock articles from the last `hours` hours,
+    /// newest first. Articles without a publish time fall back to when they
+    /// were stored.
+    pub async fn top_high_impact(
+        pool: &PgPool,
+        hours: i32,
+        limit: i64,
+    ) -> Result<Vec<TopNews>, sqlx::Error> {
+        let news = sqlx::query_as::<_, TopNews>(
+            r#"
+            SELECT origin, title, original_url, source_name, published_at
+            FROM (
+                SELECT 'forex' AS origin, a.original_title AS title, a.original_url,
+                       s.name AS source_name, a.published_at,
+                       COALESCE(a.published_at, a.created_at) AS sort_at
+                FROM news_articles a
+                JOIN news_analyses n ON n.article_id = a.id
+                LEFT JOIN news_sources s ON s.id = a.source_id
+                WHERE a.is_processed = TRUE AND n.impact_level = 'high'
+                UNION ALL
+                SELECT 'stock', title, original_url, source_name, published_at,
+                       COALESCE(published_at, created_at)
+                FROM stock_news
+                WHERE is_processed = TRUE AND impact_level = 'high'
+            ) merged
+            WHERE sort_at > NOW() - make_interval(hours => $1)
+            ORDER BY sort_at DESC NULLS LAST
+            LIMIT $2
+            "#,
+        )
+        .bind(hours)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(news)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_id_from_both_feeds_gets_distinct_keys() {
+        assert_ne!(
+            sent_key(SentSource::Stock, "42"),
+            sent_key(SentSource::Forex, "42")
+        );
+    }
+
+    #[test]
+    fn stock_ids_are_stored_without_the_legacy_prefix() {
+        assert_eq!(sent_key(SentSource::Stock, "42"), ("42", "stock"));
+        assert_eq!(sent_key(SentSource::Forex, "42"), ("42", "forex"));
+        // An id that happens to start with the old prefix is not rewritten
+        assert_eq!(
+            sent_key(SentSource::Forex, "stock_42"),
+            ("stock_42", "forex")
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::repository::news::{SentSource, sent_key};
 use sqlx::PgPool;
 
 #[derive(Debug, Clone, sqlx::FromRow)]
@@ -10,6 +11,12 @@ pub struct StockChannel {
     pub categories: Option<String>,
     pub mention_everyone: bool,
     pub is_active: bool,
+    pub webhook_url: Option<String>,
+    pub digest: bool,
+    pub digest_interval_minutes: i32,
+    pub mention_role_id: Option<i64>,
+    pub use_thread: bool,
+    pub thread_id: Option<i64>,
 }
 
 pub struct StockRepository;
@@ -35,6 +42,31 @@ impl StockRepository {
         Ok(())
     }
 
+    /// Activates `channel_id` for stock news, creating it if needed, and sets
+    /// whether high impact news pings @everyone.
+    pub async fn subscribe(
+        pool: &PgPool,
+        guild_id: u64,
+        channel_id: u64,
+        mention_everyone: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO stock_news_channels (channel_id, guild_id, mention_everyone, is_active)
+            VALUES ($1, $2, $3, TRUE)
+            ON CONFLICT (channel_id) DO UPDATE
+            SET mention_everyone = $3, is_active = TRUE, updated_at = NOW()
+            "#,
+        )
+        .bind(channel_id as i64)
+        .bind(guild_id as i64)
+        .bind(mention_everyone)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn disable_channel(pool: &PgPool, channel_id: u64) -> Result<(), sqlx::Error> {
         sqlx::query!(
             "UPDATE stock_news_channels SET is_active = FALSE WHERE channel_id = $1",
@@ -46,14 +78,48 @@ impl StockRepository {
         Ok(())
     }
 
+    /// Reactivates an existing subscription, keeping its filters and settings.
+    pub async fn enable_channel(pool: &PgPool, channel_id: u64) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE stock_news_channels SET is_active = TRUE, updated_at = NOW() WHERE channel_id = $1",
+        )
+        .bind(channel_id as i64)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     pub async fn get_active_channels(pool: &PgPool) -> Result<Vec<StockChannel>, sqlx::Error> {
-        let channels = sqlx::query_as!(
-            StockChannel,
-            r#"SELECT id, channel_id, guild_id, tickers_filter, min_impact, 
-                      categories, mention_everyone as "mention_everyone!", is_active as "is_active!" 
-               FROM stock_news_channels 
-               WHERE is_active = TRUE"#
+        let channels = sqlx::query_as::<_, StockChannel>(
+            r#"SELECT id::BIGINT AS id, channel_id, guild_id, tickers_filter, min_impact, categories,
+                      COALESCE(mention_everyone, FALSE) AS mention_everyone,
+                      COALESCE(is_active, FALSE) AS is_active, webhook_url,
+                      digest, digest_interval_minutes, mention_role_id, use_thread, thread_id
+               FROM stock_news_channels
+               WHERE is_active = TRUE"#,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(channels)
+    }
+
+    /// Every stock news subscription in `guild_id`, active or not.
+    pub async fn get_channels_for_guild(
+        pool: &PgPool,
+        guild_id: u64,
+    ) -> Result<Vec<StockChannel>, sqlx::Error> {
+        let channels = sqlx::query_as::<_, StockChannel>(
+            r#"SELECT id::BIGINT AS id, channel_id, guild_id, tickers_filter, min_impact, categories,
+                      COALESCE(mention_everyone, FALSE) AS mention_everyone,
+                      COALESCE(is_active, FALSE) AS is_active, webhook_url,
+                      digest, digest_interval_minutes, mention_role_id, use_thread, thread_id
+               FROM stock_news_channels
+               WHERE guild_id = $1
+               ORDER BY id"#,
         )
+        .bind(guild_id as i64)
         .fetch_all(pool)
         .await?;
 
@@ -64,26 +130,82 @@ impl StockRepository {
         pool: &PgPool,
         channel_id: u64,
     ) -> Result<Option<StockChannel>, sqlx::Error> {
-        let channel = sqlx::query_as!(
-            StockChannel,
-            r#"SELECT id, channel_id, guild_id, tickers_filter, min_impact, 
-                      categories, mention_everyone as "mention_everyone!", is_active as "is_active!" 
-               FROM stock_news_channels 
+        let channel = sqlx::query_as::<_, StockChannel>(
+            r#"SELECT id::BIGINT AS id, channel_id, guild_id, tickers_filter, min_impact, categories,
+                      COALESCE(mention_everyone, FALSE) AS mention_everyone,
+                      COALESCE(is_active, FALSE) AS is_active, webhook_url,
+                      digest, digest_interval_minutes, mention_role_id, use_thread, thread_id
+               FROM stock_news_channels
                WHERE channel_id = $1"#,
-            channel_id as i64,
         )
+        .bind(channel_id as i64)
         .fetch_optional(pool)
         .await?;
 
         Ok(channel)
     }
 
+    pub async fn set_webhook(
+        pool: &PgPool,
+        channel_id: u64,
+        webhook_url: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE stock_news_channels SET webhook_url = $2, updated_at = NOW() WHERE channel_id = $1",
+        )
+        .bind(channel_id as i64)
+        .bind(webhook_url)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn set_digest(
+        pool: &PgPool,
+        channel_id: u64,
+        enabled: bool,
+        interval_minutes: i32,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE stock_news_channels
+            SET digest = $2, digest_interval_minutes = $3, updated_at = NOW()
+            WHERE channel_id = $1
+            "#,
+        )
+        .bind(channel_id as i64)
+        .bind(enabled)
+        .bind(interval_minutes)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn set_mention_role(
+        pool: &PgPool,
+        channel_id: u64,
+        role_id: Option<u64>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE stock_news_channels SET mention_role_id = $2, updated_at = NOW() WHERE channel_id = $1",
+        )
+        .bind(channel_id as i64)
+        .bind(role_id.map(|id| id as i64))
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     pub async fn is_stock_news_sent(pool: &PgPool, news_id: &str) -> Result<bool, sqlx::Error> {
-        let prefixed_id = format!("stock_{}", news_id);
-        let count = sqlx::query_scalar!(
-            r#"SELECT COUNT(*) as "count!" FROM forex_news_sent WHERE news_id = $1"#,
-            prefixed_id,
+        let (news_id, source_type) = sent_key(SentSource::Stock, news_id);
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM forex_news_sent WHERE news_id = $1 AND source_type = $2",
         )
+        .bind(news_id)
+        .bind(source_type)
         .fetch_one(pool)
         .await?;
 
@@ -95,21 +217,146 @@ impl StockRepository {
         news_id: &str,
         source: &str,
     ) -> Result<(), sqlx::Error> {
-        let prefixed_id = format!("stock_{}", news_id);
+        let (news_id, source_type) = sent_key(SentSource::Stock, news_id);
         let now = chrono::Utc::now().timestamp();
-        sqlx::query!(
+        sqlx::query(
             r#"
-            INSERT INTO forex_news_sent (news_id, source, sent_at)
-            VALUES ($1, $2, $3)
-            ON CONFLICT(news_id) DO NOTHING
+            INSERT INTO forex_news_sent (news_id, source, sent_at, source_type)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT(news_id, source_type) DO NOTHING
             "#,
-            prefixed_id,
-            source,
-            now,
         )
+        .bind(news_id)
+        .bind(source)
+        .bind(now)
+        .bind(source_type)
         .execute(pool)
         .await?;
 
         Ok(())
     }
+
+    pub async fn cleanup_old_stock_news(pool: &PgPool, days: i64) -> Result<u64, sqlx::Error> {
+        let cutoff = chrono::Utc::now().timestamp() - (days * 86400);
+        let result =
+            sqlx::query("DELETE FROM forex_news_sent WHERE sent_at < $1 AND source_type = $2")
+                .bind(cutoff)
+                .bind(SentSource::Stock.as_str())
+                .execute(pool)
+                .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Tickers mentioned in stored stock news, most recently seen first.
+    pub async fn distinct_recent_tickers(
+        pool: &PgPool,
+        limit: i64,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let tickers = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT ticker FROM (
+                SELECT UPPER(TRIM(t)) AS ticker,
+                       MAX(COALESCE(published_at, created_at)) AS last_seen
+                FROM stock_news, unnest(string_to_array(tickers, ',')) AS t
+                WHERE tickers IS NOT NULL
+                GROUP BY 1
+            ) recent
+            WHERE ticker <> ''
+            ORDER BY last_seen DESC NULLS LAST
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(tickers)
+    }
+
+    /// Articles per source over the last `hours` hours, busiest first and
+    /// ties ordered by name.
+    pub async fn source_counts(
+        pool: &PgPool,
+        hours: i32,
+    ) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        let counts = sqlx::query_as::<_, (String, i64)>(
+            r#"
+            SELECT COALESCE(source_name, 'Unknown') AS source, COUNT(*) AS count
+            FROM stock_news
+            WHERE COALESCE(published_at, created_at) > NOW() - make_interval(hours => $1)
+            GROUP BY source
+            ORDER BY count DESC, source ASC
+            "#,
+        )
+        .bind(hours)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(counts)
+    }
+
+    /// Turns thread delivery on or off; returns false when the channel is
+    /// not subscribed.
+    pub async fn set_use_thread(
+        pool: &PgPool,
+        channel_id: u64,
+        enabled: bool,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE stock_news_channels SET use_thread = $2, updated_at = NOW() WHERE channel_id = $1",
+        )
+        .bind(channel_id as i64)
+        .bind(enabled)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn set_thread(
+        pool: &PgPool,
+        channel_id: u64,
+        thread_id: Option<u64>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE stock_news_channels SET thread_id = $2 WHERE channel_id = $1")
+            .bind(channel_id as i64)
+            .bind(thread_id.map(|id| id as i64))
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::ForexRepository;
+
+    #[sqlx::test]
+    #[ignore = "needs DATABASE_URL pointing at a Postgres server"]
+    async fn stock_and_forex_ids_are_tracked_separately(pool: PgPool) {
+        StockRepository::insert_stock_news(&pool, "42", "test")
+            .await
+            .unwrap();
+
+        assert!(
+            StockRepository::is_stock_news_sent(&pool, "42")
+                .await
+                .unwrap()
+        );
+        assert!(!ForexRepository::is_news_sent(&pool, "42").await.unwrap());
+
+        ForexRepository::insert_news(&pool, "42", "test")
+            .await
+            .unwrap();
+        assert!(ForexRepository::is_news_sent(&pool, "42").await.unwrap());
+
+        let deleted = StockRepository::cleanup_old_stock_news(&pool, -1)
+            .await
+            .unwrap();
+        assert_eq!(deleted, 1);
+        assert!(ForexRepository::is_news_sent(&pool, "42").await.unwrap());
+    }
 }
@@ -0,0 +1,88 @@
+use sqlx::PgPool;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SymbolPolicyEntry {
+    pub symbol: String,
+    /// `allow` or `deny`.
+    pub mode: String,
+}
+
+pub struct SymbolPolicyRepository;
+
+impl SymbolPolicyRepository {
+    /// Adds or moves `symbol` to the allow or deny list.
+    pub async fn set(
+        pool: &PgPool,
+        guild_id: u64,
+        symbol: &str,
+        allow: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_symbol_policy (guild_id, symbol, mode)
+            VALUES ($1, $2, $3)
+            ON CONFLICT(guild_id, symbol) DO UPDATE SET mode = EXCLUDED.mode
+            "#,
+        )
+        .bind(guild_id as i64)
+        .bind(symbol)
+        .bind(if allow { "allow" } else { "deny" })
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn clear(pool: &PgPool, guild_id: u64) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM guild_symbol_policy WHERE guild_id = $1")
+            .bind(guild_id as i64)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn list(pool: &PgPool, guild_id: u64) -> Result<Vec<SymbolPolicyEntry>, sqlx::Error> {
+        let entries = sqlx::query_as::<_, SymbolPolicyEntry>(
+            "SELECT symbol, mode FROM guild_symbol_policy WHERE guild_id = $1 ORDER BY mode, symbol",
+        )
+        .bind(guild_id as i64)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    /// False when `symbol` is denied, or an allowlist exists without it.
+    /// Guilds without entries allow everything.
+    pub async fn is_allowed(
+        pool: &PgPool,
+        guild_id: u64,
+        symbol: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let allowed = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT NOT EXISTS (
+                       SELECT 1 FROM guild_symbol_policy
+                       WHERE guild_id = $1 AND symbol = $2 AND mode = 'deny'
+                   )
+                   AND (
+                       NOT EXISTS (
+                           SELECT 1 FROM guild_symbol_policy
+                           WHERE guild_id = $1 AND mode = 'allow'
+                       )
+                       OR EXISTS (
+                           SELECT 1 FROM guild_symbol_policy
+                           WHERE guild_id = $1 AND symbol = $2 AND mode = 'allow'
+                       )
+                   )
+            "#,
+        )
+        .bind(guild_id as i64)
+        .bind(symbol)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(allowed)
+    }
+}
@@ -0,0 +1,82 @@
+use sqlx::PgPool;
+
+/// Aggregated usage of one command over a time window.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct CommandStat {
+    pub command_name: String,
+    pub uses: i64,
+    pub failures: i64,
+    pub avg_latency_ms: f64,
+}
+
+pub struct UsageRepository;
+
+impl UsageRepository {
+    pub async fn record(
+        pool: &PgPool,
+        command_name: &str,
+        guild_id: Option<u64>,
+        user_id: u64,
+        success: bool,
+        latency_ms: i32,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO command_usage (command_name, guild_id, user_id, success, latency_ms)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(command_name)
+        .bind(guild_id.map(|id| id as i64))
+        .bind(user_id as i64)
+        .bind(success)
+        .bind(latency_ms)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Most used commands in the last `hours` hours.
+    pub async fn top_commands(
+        pool: &PgPool,
+        hours: i32,
+        limit: i64,
+    ) -> Result<Vec<CommandStat>, sqlx::Error> {
+        let stats = sqlx::query_as::<_, CommandStat>(
+            r#"
+            SELECT command_name,
+                   COUNT(*) AS uses,
+                   COUNT(*) FILTER (WHERE NOT success) AS failures,
+                   AVG(latency_ms)::FLOAT8 AS avg_latency_ms
+            FROM command_usage
+            WHERE created_at > NOW() - make_interval(hours => $1)
+            GROUP BY command_name
+            ORDER BY uses DESC, command_name
+            LIMIT $2
+            "#,
+        )
+        .bind(hours)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(stats)
+    }
+
+    /// Total invocations and average latency in the last `hours` hours.
+    pub async fn totals(pool: &PgPool, hours: i32) -> Result<(i64, Option<f64>), sqlx::Error> {
+        let totals = sqlx::query_as::<_, (i64, Option<f64>)>(
+            r#"
+            SELECT COUNT(*), AVG(latency_ms)::FLOAT8
+            FROM command_usage
+            WHERE created_at > NOW() - make_interval(hours => $1)
+            "#,
+        )
+        .bind(hours)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(totals)
+    }
+}
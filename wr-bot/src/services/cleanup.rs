@@ -0,0 +1,33 @@
+use crate::repository::{CalendarRepository, DbPool, ForexRepository, StockRepository};
+use std::time::Duration;
+
+const CLEANUP_INTERVAL_SECS: u64 = 24 * 60 * 60;
+
+/// Periodically prunes the sent-news and sent-event dedup tables.
+pub fn start_cleanup_scheduler(db: DbPool, retention_days: i64) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(CLEANUP_INTERVAL_SECS));
+
+        loop {
+            interval.tick().await;
+            run_cleanup(&db, retention_days).await;
+        }
+    });
+}
+
+async fn run_cleanup(db: &DbPool, retention_days: i64) {
+    match ForexRepository::cleanup_old_news(db, retention_days).await {
+        Ok(deleted) => println!("[CLEANUP] forex_news_sent: {} forex rows deleted", deleted),
+        Err(e) => eprintln!("[CLEANUP] Failed to clean forex news: {}", e),
+    }
+
+    match StockRepository::cleanup_old_stock_news(db, retention_days).await {
+        Ok(deleted) => println!("[CLEANUP] forex_news_sent: {} stock rows deleted", deleted),
+        Err(e) => eprintln!("[CLEANUP] Failed to clean stock news: {}", e),
+    }
+
+    match CalendarRepository::cleanup_old_events(db, retention_days).await {
+        Ok(deleted) => println!("[CLEANUP] calendar_events_sent: {} rows deleted", deleted),
+        Err(e) => eprintln!("[CLEANUP] Failed to clean calendar events: {}", e),
+    }
+}
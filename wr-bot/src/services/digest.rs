@@ -0,0 +1,185 @@
+use crate::utils::webhook;
+use parking_lot::Mutex;
+use poise::serenity_prelude::{ChannelId, CreateEmbed, CreateEmbedFooter, Http};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+const MAX_DIGEST_ITEMS: usize = 25;
+const MAX_DESCRIPTION_LEN: usize = 4000;
+
+#[derive(Debug, Clone)]
+pub struct DigestItem {
+    pub title: String,
+    pub url: String,
+    pub source: String,
+}
+
+/// Which feed a digest belongs to; a channel can receive both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DigestKind {
+    Forex,
+    Stock,
+}
+
+impl DigestKind {
+    fn title(self) -> &'static str {
+        match self {
+            DigestKind::Forex => "Forex News Digest",
+            DigestKind::Stock => "Ringkasan Berita Saham",
+        }
+    }
+
+    fn color(self) -> u32 {
+        match self {
+            DigestKind::Forex => 0x009688,
+            DigestKind::Stock => 0x2962FF,
+        }
+    }
+}
+
+struct PendingDigest {
+    items: Vec<DigestItem>,
+    webhook_url: Option<String>,
+    interval: Duration,
+    started: Instant,
+}
+
+/// Buffers news per channel for channels in digest mode and posts one
+/// consolidated embed per interval.
+pub struct DigestBuffer {
+    http: Arc<Http>,
+    pending: Mutex<HashMap<(ChannelId, DigestKind), PendingDigest>>,
+}
+
+impl DigestBuffer {
+    pub fn new(http: Arc<Http>) -> Self {
+        Self {
+            http,
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn push(
+        &self,
+        channel_id: ChannelId,
+        kind: DigestKind,
+        interval_minutes: i32,
+        webhook_url: Option<String>,
+        item: DigestItem,
+    ) {
+        let interval = Duration::from_secs(interval_minutes.max(1) as u64 * 60);
+        let mut pending = self.pending.lock();
+        let digest = pending
+            .entry((channel_id, kind))
+            .or_insert_with(|| PendingDigest {
+                items: Vec::new(),
+                webhook_url: None,
+                interval,
+                started: Instant::now(),
+            });
+
+        digest.interval = interval;
+        digest.webhook_url = webhook_url;
+        digest.items.push(item);
+    }
+
+    /// Posts every digest whose interval has elapsed since its first item.
+    pub async fn flush_due(&self) {
+        let due: Vec<_> = {
+            let mut pending = self.pending.lock();
+            let keys: Vec<_> = pending
+                .iter()
+                .filter(|(_, d)| d.started.elapsed() >= d.interval)
+                .map(|(k, _)| *k)
+                .collect();
+            keys.into_iter()
+                .filter_map(|k| pending.remove(&k).map(|d| (k, d)))
+                .collect()
+        };
+
+        for ((channel_id, kind), digest) in due {
+            self.send(channel_id, kind, digest).await;
+        }
+    }
+
+    /// Posts everything still buffered, used on shutdown.
+    pub async fn flush_all(&self) {
+        let all: Vec<_> = self.pending.lock().drain().collect();
+
+        for ((channel_id, kind), digest) in all {
+            self.send(channel_id, kind, digest).await;
+        }
+    }
+
+    async fn send(&self, channel_id: ChannelId, kind: DigestKind, digest: PendingDigest) {
+        if digest.items.is_empty() {
+            return;
+        }
+
+        let mut description = String::new();
+        let mut shown = 0;
+
+        for item in digest.items.iter().take(MAX_DIGEST_ITEMS) {
+            let line = format!("**[{}]({})**\n{}\n\n", item.title, item.url, item.source);
+            if description.len() + line.len() > MAX_DESCRIPTION_LEN {
+                break;
+            }
+            description.push_str(&line);
+            shown += 1;
+        }
+
+        let remaining = digest.items.len() - shown;
+        let footer = if remaining > 0 {
+            format!("{} articles | +{} more", digest.items.len(), remaining)
+        } else {
+            format!("{} articles", digest.items.len())
+        };
+
+        let embed = CreateEmbed::new()
+            .title(kind.title())
+            .description(description)
+            .color(kind.color())
+            .footer(CreateEmbedFooter::new(footer));
+
+        if let Err(e) = webhook::send_embed(
+            &self.http,
+            channel_id,
+            digest.webhook_url.as_deref(),
+            None,
+            embed,
+        )
+        .await
+        {
+            eprintln!(
+                "[DIGEST] Failed to send digest to channel {}: {}",
+                channel_id.get(),
+                e
+            );
+        }
+    }
+}
+
+static DIGEST_BUFFER: OnceLock<Arc<DigestBuffer>> = OnceLock::new();
+
+/// Creates the global digest buffer and starts its flush loop.
+pub fn init_digest_buffer(http: Arc<Http>, check_interval: Duration) -> Arc<DigestBuffer> {
+    let buffer = DIGEST_BUFFER
+        .get_or_init(|| Arc::new(DigestBuffer::new(http)))
+        .clone();
+
+    let flusher = buffer.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(check_interval);
+        loop {
+            interval.tick().await;
+            flusher.flush_due().await;
+        }
+    });
+
+    buffer
+}
+
+pub fn get_digest_buffer() -> Option<Arc<DigestBuffer>> {
+    DIGEST_BUFFER.get().cloned()
+}
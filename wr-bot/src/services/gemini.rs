@@ -2,10 +2,104 @@ use gemini_rust::Gemini;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::{RwLock, Semaphore, SemaphorePermit, watch};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 
+const DEFAULT_MAX_CONCURRENT: usize = 4;
+
+/// Shared across all `GeminiService` instances since commands create their own.
+static REQUEST_SLOTS: OnceLock<Semaphore> = OnceLock::new();
+
+fn request_slots() -> &'static Semaphore {
+    REQUEST_SLOTS.get_or_init(|| {
+        let permits = std::env::var("GEMINI_MAX_CONCURRENT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(DEFAULT_MAX_CONCURRENT);
+        Semaphore::new(permits)
+    })
+}
+
+#[derive(Debug)]
+pub enum GeminiError {
+    /// The API answered 429 / RESOURCE_EXHAUSTED.
+    RateLimited { retry_after: Option<Duration> },
+}
+
+impl fmt::Display for GeminiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeminiError::RateLimited {
+                retry_after: Some(d),
+            } => write!(f, "Gemini rate limited, retry after {}s", d.as_secs()),
+            GeminiError::RateLimited { retry_after: None } => write!(f, "Gemini rate limited"),
+        }
+    }
+}
+
+impl std::error::Error for GeminiError {}
+
+/// Maps quota errors surfaced by the gemini client into [`GeminiError::RateLimited`].
+fn classify_error(
+    e: Box<dyn std::error::Error + Send + Sync>,
+) -> Box<dyn std::error::Error + Send + Sync> {
+    let message = e.to_string();
+    if message.contains("429") || message.contains("RESOURCE_EXHAUSTED") {
+        return Box::new(GeminiError::RateLimited {
+            retry_after: parse_retry_delay(&message),
+        });
+    }
+    e
+}
+
+/// Appends the text carried by one server-sent event of a streamed response.
+fn append_sse_event(
+    event: &str,
+    text: &mut String,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    for data in event.lines().filter_map(|l| l.strip_prefix("data:")) {
+        let Ok(parsed) = serde_json::from_str::<GeminiResponse>(data.trim()) else {
+            continue;
+        };
+
+        if let Some(error) = parsed.error {
+            return Err(format!("Gemini API Error: {}", error.message).into());
+        }
+
+        let parts = parsed
+            .candidates
+            .and_then(|c| c.into_iter().next())
+            .map(|c| c.content.parts)
+            .unwrap_or_default();
+
+        text.extend(parts.into_iter().filter_map(|p| p.text));
+    }
+
+    Ok(())
+}
+
+/// Removes and returns the next complete server-sent event from `buffer`.
+///
+/// Works on raw bytes so a multi-byte character split across network chunks
+/// is only decoded once its whole event has arrived.
+fn take_sse_event(buffer: &mut Vec<u8>) -> Option<String> {
+    let end = buffer.windows(2).position(|w| w == b"\n\n")?;
+    let event: Vec<u8> = buffer.drain(..end + 2).collect();
+    Some(String::from_utf8_lossy(&event).into_owned())
+}
+
+/// Extracts the delay from `"retryDelay": "31s"` or "Please retry in 31.5s".
+fn parse_retry_delay(message: &str) -> Option<Duration> {
+    let re =
+        regex_lite::Regex::new(r#"(?:retryDelay"?\s*:\s*"|retry in )(\d+(?:\.\d+)?)s"#).ok()?;
+    let secs: f64 = re.captures(message)?.get(1)?.as_str().parse().ok()?;
+    Some(Duration::from_secs_f64(secs.ceil()))
+}
+
 #[derive(Serialize)]
 struct GeminiRequest {
     contents: Vec<Content>,
@@ -41,11 +135,11 @@ struct InlineData {
 #[derive(Deserialize)]
 struct GeminiResponse {
     candidates: Option<Vec<Candidate>>,
-    error: Option<GeminiError>,
+    error: Option<ApiError>,
 }
 
 #[derive(Deserialize)]
-struct GeminiError {
+struct ApiError {
     message: String,
 }
 
@@ -90,6 +184,12 @@ impl GeminiService {
     fn create_client(&self) -> Result<Gemini, Box<dyn std::error::Error + Send + Sync>> {
         Ok(Gemini::new(&self.api_key)?)
     }
+
+    async fn acquire_slot(
+        &self,
+    ) -> Result<SemaphorePermit<'static>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(request_slots().acquire().await?)
+    }
     
     fn get_api_url(&self) -> String {
         format!(
@@ -98,18 +198,98 @@ impl GeminiService {
         )
     }
 
+    fn get_stream_url(&self) -> String {
+        format!(
+            "https://generativelanguage.googleapis.com/v1beta/models/{}:streamGenerateContent?alt=sse&key={}",
+            self.model, self.api_key
+        )
+    }
+
+    /// Like [`generate`](Self::generate), but publishes the accumulated text to
+    /// `partial` as chunks arrive. Falls back to a single-shot request when the
+    /// streaming endpoint is unavailable.
+    pub async fn generate_stream(
+        &self,
+        prompt: &str,
+        partial: watch::Sender<String>,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let request = GeminiRequest {
+            contents: vec![Content {
+                parts: vec![Part::Text {
+                    text: prompt.to_string(),
+                }],
+                role: Some("user".to_string()),
+            }],
+            system_instruction: if !self.system_prompt.is_empty() {
+                Some(SystemInstruction {
+                    parts: vec![Part::Text {
+                        text: self.system_prompt.clone(),
+                    }],
+                })
+            } else {
+                None
+            },
+        };
+
+        let slot = self.acquire_slot().await?;
+        let mut response = self
+            .http_client
+            .post(self.get_stream_url())
+            .json(&request)
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .map(Duration::from_secs);
+            return Err(Box::new(GeminiError::RateLimited { retry_after }));
+        }
+
+        if !response.status().is_success() {
+            drop(slot);
+            return self.generate(prompt).await;
+        }
+
+        let mut text = String::new();
+        let mut buffer: Vec<u8> = Vec::new();
+
+        while let Some(chunk) = response.chunk().await? {
+            // '\r' never occurs inside a multi-byte UTF-8 sequence
+            buffer.extend(chunk.iter().filter(|&&b| b != b'\r'));
+
+            // Server-sent events are separated by a blank line
+            while let Some(event) = take_sse_event(&mut buffer) {
+                append_sse_event(&event, &mut text)?;
+                partial.send_replace(text.clone());
+            }
+        }
+        append_sse_event(&String::from_utf8_lossy(&buffer), &mut text)?;
+
+        if text.is_empty() {
+            return Err("No response text from Gemini".into());
+        }
+
+        Ok(text)
+    }
+
     pub async fn generate(
         &self,
         prompt: &str,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let client = self.create_client()?;
+        let _slot = self.acquire_slot().await?;
 
         let response = client
             .generate_content()
             .with_system_prompt(&self.system_prompt)
             .with_user_message(prompt)
             .execute()
-            .await?;
+            .await
+            .map_err(|e| classify_error(e.into()))?;
 
         let text = response.text();
         if text.is_empty() {
@@ -157,7 +337,11 @@ impl GeminiService {
             }
         }
 
-        let response = builder.execute().await?;
+        let _slot = self.acquire_slot().await?;
+        let response = builder
+            .execute()
+            .await
+            .map_err(|e| classify_error(e.into()))?;
 
         let text = response.text();
         if text.is_empty() {
@@ -242,12 +426,23 @@ impl GeminiService {
             },
         };
 
+        let _slot = self.acquire_slot().await?;
         let response = self.http_client
             .post(&self.get_api_url())
             .json(&request)
             .send()
             .await?;
 
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .map(Duration::from_secs);
+            return Err(Box::new(GeminiError::RateLimited { retry_after }));
+        }
+
         let gemini_response: GeminiResponse = response.json().await?;
         
         if let Some(error) = gemini_response.error {
@@ -378,3 +573,25 @@ Berikan analisis lengkap berdasarkan instruksi di atas."#,
         self.generate(&prompt).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_multibyte_character_survives_chunking() {
+        let event =
+            "data: {\"candidates\":[{\"content\":{\"parts\":[{\"text\":\"Halo 👋 é\"}]}}]}\n\n";
+        let bytes = event.as_bytes();
+        let split = event.find('👋').unwrap() + 2;
+
+        let mut buffer = bytes[..split].to_vec();
+        assert_eq!(take_sse_event(&mut buffer), None);
+        buffer.extend_from_slice(&bytes[split..]);
+
+        let mut text = String::new();
+        append_sse_event(&take_sse_event(&mut buffer).unwrap(), &mut text).unwrap();
+        assert_eq!(text, "Halo 👋 é");
+        assert!(buffer.is_empty());
+    }
+}
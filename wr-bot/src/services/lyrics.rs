@@ -0,0 +1,247 @@
+use serde::Deserialize;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub const DEFAULT_LYRICS_API_URL: &str = "https://lrclib.net";
+
+/// Bracketed title parts containing one of these words are dropped before
+/// searching, e.g. "(Official Video)" or "[Lyric Video]".
+const TITLE_NOISE: &[&str] = &[
+    "official",
+    "video",
+    "audio",
+    "lyric",
+    "lyrics",
+    "mv",
+    "m/v",
+    "visualizer",
+    "hd",
+    "4k",
+    "remaster",
+    "remastered",
+];
+
+#[derive(Debug, Clone)]
+pub struct Lyrics {
+    pub title: String,
+    pub artist: String,
+    /// Empty for instrumental tracks.
+    pub text: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LrclibTrack {
+    track_name: String,
+    artist_name: String,
+    #[serde(default)]
+    instrumental: bool,
+    plain_lyrics: Option<String>,
+}
+
+/// HTTP client for the LRCLIB lyrics API.
+pub struct LyricsService {
+    base_url: String,
+    http: reqwest::Client,
+}
+
+impl LyricsService {
+    pub fn new(base_url: &str) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent(concat!("worm/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            http,
+        }
+    }
+
+    /// Lyrics for a track, searching by title and (when known) artist.
+    ///
+    /// `Ok(None)` means the provider has no lyrics for it; an error means
+    /// the provider could not be reached or answered badly.
+    pub async fn search_track(
+        &self,
+        title: &str,
+        artist: Option<&str>,
+    ) -> Result<Option<Lyrics>, reqwest::Error> {
+        let mut query = vec![("track_name", title)];
+        if let Some(artist) = artist {
+            query.push(("artist_name", artist));
+        }
+        self.search(&query).await
+    }
+
+    /// Lyrics for a free-text query like "artist title".
+    pub async fn search_query(&self, query: &str) -> Result<Option<Lyrics>, reqwest::Error> {
+        self.search(&[("q", query)]).await
+    }
+
+    async fn search(&self, query: &[(&str, &str)]) -> Result<Option<Lyrics>, reqwest::Error> {
+        let tracks: Vec<LrclibTrack> = self
+            .http
+            .get(format!("{}/api/search", self.base_url))
+            .query(query)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let lyrics = tracks.into_iter().find_map(|track| {
+            let text = track.plain_lyrics.unwrap_or_default();
+            (track.instrumental || !text.trim().is_empty()).then(|| Lyrics {
+                title: track.track_name,
+                artist: track.artist_name,
+                text: text.trim().to_string(),
+            })
+        });
+
+        Ok(lyrics)
+    }
+}
+
+/// Turns a video title and channel name into a (title, artist) search.
+///
+/// Drops noise like "(Official Video)" and "feat." credits, and splits
+/// "Artist - Title" uploads. Channel suffixes like " - Topic" and "VEVO"
+/// are removed from the artist. A title that is nothing but noise is kept
+/// as uploaded.
+pub fn clean_track(raw_title: &str, author: &str) -> (String, Option<String>) {
+    let mut cleaned = String::with_capacity(raw_title.len());
+    let mut rest = raw_title;
+
+    while let Some(start) = rest.find(['(', '[']) {
+        let close = if rest[start..].starts_with('(') {
+            ')'
+        } else {
+            ']'
+        };
+        let Some(len) = rest[start..].find(close) else {
+            break;
+        };
+        let inner = rest[start + 1..start + len].to_lowercase();
+        let noisy = inner
+            .split_whitespace()
+            .any(|word| TITLE_NOISE.contains(&word))
+            || inner.starts_with("feat")
+            || inner.starts_with("ft.");
+
+        cleaned.push_str(&rest[..start]);
+        if !noisy {
+            cleaned.push_str(&rest[start..=start + len]);
+        }
+        rest = &rest[start + len + 1..];
+    }
+    cleaned.push_str(rest);
+
+    // Featured artists outside brackets, e.g. "Song ft. Someone". ASCII
+    // lowercasing keeps byte offsets valid for truncating `cleaned`
+    let lower = cleaned.to_ascii_lowercase();
+    if let Some(idx) = [" feat. ", " ft. ", " feat ", " ft "]
+        .iter()
+        .filter_map(|marker| lower.find(marker))
+        .min()
+    {
+        cleaned.truncate(idx);
+    }
+
+    let author = author.trim();
+    let author = author
+        .strip_suffix(" - Topic")
+        .or_else(|| author.strip_suffix("VEVO"))
+        .unwrap_or(author)
+        .trim();
+
+    let (artist, title) = match cleaned.split_once(" - ") {
+        Some((artist, title)) => (Some(artist.trim().to_string()), title),
+        None => (
+            (!author.is_empty()).then(|| author.to_string()),
+            cleaned.as_str(),
+        ),
+    };
+
+    let mut title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    // A title that was all noise, e.g. "(Official Video)", searches as-is
+    if title.is_empty() {
+        title = raw_title.split_whitespace().collect::<Vec<_>>().join(" ");
+    }
+    (title, artist)
+}
+
+static LYRICS_SERVICE: OnceLock<LyricsService> = OnceLock::new();
+
+pub fn init_lyrics_service(base_url: &str) {
+    let _ = LYRICS_SERVICE.set(LyricsService::new(base_url));
+}
+
+pub fn get_lyrics_service() -> Option<&'static LyricsService> {
+    LYRICS_SERVICE.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn noise_and_featured_artists_are_dropped() {
+        assert_eq!(
+            clean_track("Song Name (Official Music Video) [4K]", "Artist"),
+            ("Song Name".to_string(), Some("Artist".to_string()))
+        );
+        assert_eq!(
+            clean_track("Song Name ft. Someone", "Artist"),
+            ("Song Name".to_string(), Some("Artist".to_string()))
+        );
+        assert_eq!(
+            clean_track("Song Name (feat. Someone)", "Artist"),
+            ("Song Name".to_string(), Some("Artist".to_string()))
+        );
+    }
+
+    #[test]
+    fn meaningful_brackets_are_kept() {
+        assert_eq!(clean_track("Song (Acoustic)", "").0, "Song (Acoustic)");
+        assert_eq!(clean_track("Song (unclosed", "").0, "Song (unclosed");
+    }
+
+    #[test]
+    fn artist_comes_from_the_title_or_a_cleaned_channel_name() {
+        assert_eq!(
+            clean_track("Artist - Song (Lyrics)", "Some Uploader"),
+            ("Song".to_string(), Some("Artist".to_string()))
+        );
+        assert_eq!(
+            clean_track("Song", "Artist - Topic").1.as_deref(),
+            Some("Artist")
+        );
+        assert_eq!(
+            clean_track("Song", "ArtistVEVO").1.as_deref(),
+            Some("Artist")
+        );
+        assert_eq!(clean_track("Song", "  ").1, None);
+    }
+
+    #[test]
+    fn all_noise_title_falls_back_to_the_raw_title() {
+        assert_eq!(
+            clean_track("(Official Video)", "Artist").0,
+            "(Official Video)"
+        );
+        assert_eq!(clean_track("  [HD]  ", "").0, "[HD]");
+    }
+
+    #[test]
+    fn multibyte_titles_do_not_panic() {
+        assert_eq!(clean_track("Ünïcödé FT. Someone", "").0, "Ünïcödé");
+        assert_eq!(
+            clean_track("夜に駆ける (Official Video)", "YOASOBI").0,
+            "夜に駆ける"
+        );
+    }
+}
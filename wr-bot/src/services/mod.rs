@@ -1,9 +1,15 @@
 pub mod ai;
+pub mod cleanup;
+pub mod digest;
 
 pub mod gemini;
+pub mod lyrics;
 pub mod music;
+pub mod mutes;
+pub mod news_api;
 pub mod news_ws;
 pub mod stock_ws;
+pub mod ws_metrics;
 
 pub mod youtube;
 
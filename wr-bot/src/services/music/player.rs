@@ -1,4 +1,4 @@
-use crate::services::music::queue::{LoopMode, MusicQueue, QueuedTrack};
+use crate::services::music::queue::{IdleReason, LoopMode, MusicQueue, QueuedTrack};
 use lavalink_rs::client::LavalinkClient;
 use lavalink_rs::model::track::TrackData;
 use once_cell::sync::OnceCell;
@@ -7,6 +7,7 @@ use serenity::all::{ChannelId, GuildId, Http, UserId};
 use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub type GuildQueues = Arc<RwLock<HashMap<GuildId, MusicQueue>>>;
 
@@ -109,6 +110,20 @@ impl MusicPlayer {
         let mut queues = self.queues.write();
         if let Some(queue) = queues.get_mut(&guild_id) {
             queue.current = track;
+            queue.skip_votes.clear();
+        }
+    }
+
+    /// Records a skip vote for the current track and returns the vote count.
+    /// Votes are cleared whenever the current track changes.
+    pub fn add_skip_vote(&self, guild_id: GuildId, user_id: u64) -> usize {
+        let mut queues = self.queues.write();
+        match queues.get_mut(&guild_id) {
+            Some(queue) => {
+                queue.skip_votes.insert(user_id);
+                queue.skip_votes.len()
+            }
+            None => 0,
         }
     }
 
@@ -286,16 +301,37 @@ impl MusicPlayer {
         }
     }
 
-    /// Get all guilds that have been idle (no current track) for the given duration
+    /// Mark whether the bot is alone in its voice channel. Starts the
+    /// no-listener timer, or cancels it and resets the idle timer when
+    /// someone joins again.
+    pub fn set_alone(&self, guild_id: GuildId, alone: bool) {
+        let mut queues = self.queues.write();
+        if let Some(queue) = queues.get_mut(&guild_id) {
+            if alone {
+                queue
+                    .alone_since
+                    .get_or_insert_with(std::time::Instant::now);
+            } else {
+                queue.alone_since = None;
+                queue.touch_activity();
+            }
+        }
+    }
+
+    /// Get all guilds that have been idle (no current track or no listeners)
+    /// for the given duration
     pub fn get_idle_guilds(
         &self,
         idle_duration: std::time::Duration,
-    ) -> Vec<(GuildId, Option<serenity::all::ChannelId>)> {
+    ) -> Vec<(GuildId, Option<serenity::all::ChannelId>, IdleReason)> {
         self.queues
             .read()
             .iter()
-            .filter(|(_, queue)| queue.is_idle_for(idle_duration))
-            .map(|(guild_id, queue)| (*guild_id, queue.text_channel_id))
+            .filter_map(|(guild_id, queue)| {
+                queue
+                    .idle_reason(idle_duration)
+                    .map(|reason| (*guild_id, queue.text_channel_id, reason))
+            })
             .collect()
     }
 
@@ -312,6 +348,52 @@ impl MusicPlayer {
         self.lavalink.get_player_context(lavalink_guild_id)
     }
 
+    /// Elapsed time of the current track and its length, which is `None` for
+    /// live streams.
+    pub async fn current_track_progress(
+        &self,
+        guild_id: GuildId,
+    ) -> Option<(Duration, Option<Duration>)> {
+        let player = self.get_player_context(guild_id)?.get_player().await.ok()?;
+        let info = &player.track?.info;
+
+        let length = (!info.is_stream).then(|| Duration::from_millis(info.length));
+        let elapsed = Duration::from_millis(player.state.position);
+
+        Some((length.map_or(elapsed, |l| elapsed.min(l)), length))
+    }
+
+    /// Jumps to `position` in the current track and returns the track length.
+    ///
+    /// Fails with a user-facing message when nothing is playing, the source
+    /// cannot seek (e.g. live streams) or `position` is past the end.
+    pub async fn seek(&self, guild_id: GuildId, position: Duration) -> Result<Duration, String> {
+        let player_ctx = self
+            .get_player_context(guild_id)
+            .ok_or_else(|| "The bot is not playing music".to_string())?;
+        let current = self
+            .get_current(guild_id)
+            .ok_or_else(|| "No song is currently playing".to_string())?;
+
+        let info = &current.track.info;
+        if info.is_stream || !info.is_seekable {
+            return Err("This source can't be seeked".to_string());
+        }
+
+        let length = Duration::from_millis(info.length);
+        if position >= length {
+            return Err("That position is past the end of the track".to_string());
+        }
+
+        player_ctx
+            .set_position(position)
+            .await
+            .map_err(|e| format!("Failed to seek: {}", e))?;
+        self.touch_activity(guild_id);
+
+        Ok(length)
+    }
+
     pub async fn create_player_with_connection(
         &self,
         guild_id: GuildId,
@@ -1,6 +1,6 @@
 use lavalink_rs::model::track::TrackData;
 use serenity::all::ChannelId;
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 use std::time::Instant;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -16,6 +16,15 @@ impl Default for LoopMode {
     }
 }
 
+/// Why an idle guild is being disconnected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleReason {
+    /// Nothing has played since the queue ran out.
+    NothingPlaying,
+    /// Everyone else left the voice channel.
+    NoListeners,
+}
+
 #[derive(Debug, Clone)]
 pub struct MusicQueue {
     pub tracks: VecDeque<QueuedTrack>,
@@ -30,7 +39,9 @@ pub struct MusicQueue {
     pub last_video_id: Option<String>,
     pub played_video_ids: VecDeque<String>,
     pub text_channel_id: Option<ChannelId>,
-    pub last_activity: Instant, // Track when music was last active
+    pub last_activity: Instant,       // Track when music was last active
+    pub alone_since: Option<Instant>, // Set while no one else is in the voice channel
+    pub skip_votes: HashSet<u64>,     // Users voting to skip the current track
 }
 
 #[derive(Debug, Clone)]
@@ -62,6 +73,8 @@ impl MusicQueue {
             played_video_ids: VecDeque::with_capacity(20),
             text_channel_id: None,
             last_activity: Instant::now(),
+            alone_since: None,
+            skip_votes: HashSet::new(),
         }
     }
 
@@ -70,9 +83,18 @@ impl MusicQueue {
         self.last_activity = Instant::now();
     }
 
-    /// Check if queue has been idle for the given duration
-    pub fn is_idle_for(&self, duration: std::time::Duration) -> bool {
-        self.current.is_none() && self.last_activity.elapsed() >= duration
+    /// Why the queue counts as idle after `duration`, if it does
+    pub fn idle_reason(&self, duration: std::time::Duration) -> Option<IdleReason> {
+        if self
+            .alone_since
+            .is_some_and(|since| since.elapsed() >= duration)
+        {
+            Some(IdleReason::NoListeners)
+        } else if self.current.is_none() && self.last_activity.elapsed() >= duration {
+            Some(IdleReason::NothingPlaying)
+        } else {
+            None
+        }
     }
 
     pub fn add(&mut self, track: QueuedTrack) {
@@ -80,6 +102,8 @@ impl MusicQueue {
     }
 
     pub fn next_with_loop_info(&mut self) -> (Option<QueuedTrack>, bool) {
+        self.skip_votes.clear();
+
         if self.loop_mode == LoopMode::Track || self.is_looping {
             if let Some(current) = &self.current {
                 return (Some(current.clone()), true);
@@ -117,6 +141,7 @@ impl MusicQueue {
         self.tracks.clear();
         self.played_tracks.clear();
         self.current = None;
+        self.skip_votes.clear();
     }
 
     pub fn remove(&mut self, index: usize) -> Option<QueuedTrack> {
@@ -0,0 +1,176 @@
+use crate::repository::{DbPool, Mute, MuteRepository};
+use chrono::{DateTime, Utc};
+use poise::serenity_prelude::{self as serenity, GuildId, Http, RoleId, UserId};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Backstop for timers lost to a panic or a missed wakeup.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Lifts a role mute once `unmute_at` has passed.
+///
+/// The row is re-checked when the timer fires, so a mute that was lifted or
+/// extended in the meantime is left alone.
+pub fn schedule_unmute(
+    http: Arc<Http>,
+    db: DbPool,
+    guild_id: u64,
+    user_id: u64,
+    unmute_at: DateTime<Utc>,
+) {
+    tokio::spawn(async move {
+        let delay = (unmute_at - Utc::now()).to_std().unwrap_or_default();
+        tokio::time::sleep(delay).await;
+        lift_if_expired(&http, &db, guild_id, user_id).await;
+    });
+}
+
+fn is_expired(mute: &Mute, now: DateTime<Utc>) -> bool {
+    mute.unmute_at <= now
+}
+
+/// Splits `mutes` into those that have ended by `now` and those still running.
+fn split_expired(mutes: Vec<Mute>, now: DateTime<Utc>) -> (Vec<Mute>, Vec<Mute>) {
+    mutes.into_iter().partition(|mute| is_expired(mute, now))
+}
+
+async fn lift_if_expired(http: &Http, db: &DbPool, guild_id: u64, user_id: u64) {
+    let mute = match MuteRepository::get(db, guild_id, user_id).await {
+        Ok(Some(mute)) => mute,
+        Ok(None) => return,
+        Err(e) => {
+            eprintln!(
+                "[WARN] Failed to check mute for user {} in guild {}: {}",
+                user_id, guild_id, e
+            );
+            return;
+        }
+    };
+
+    if is_expired(&mute, Utc::now()) {
+        lift(http, db, &mute).await;
+    }
+}
+
+/// Removes the mute role, and only then drops the row so a failed removal
+/// is retried by the next sweep.
+async fn lift(http: &Http, db: &DbPool, mute: &Mute) {
+    let (guild_id, user_id) = (mute.guild_id as u64, mute.user_id as u64);
+
+    match http
+        .remove_member_role(
+            GuildId::new(guild_id),
+            UserId::new(user_id),
+            RoleId::new(mute.role_id as u64),
+            Some("Mute expired"),
+        )
+        .await
+    {
+        Ok(()) => {}
+        // The member left or the role was deleted, so there is nothing to lift
+        Err(serenity::Error::Http(e)) if e.status_code().map(|s| s.as_u16()) == Some(404) => {}
+        Err(e) => {
+            eprintln!(
+                "[WARN] Failed to remove mute role from user {} in guild {}, will retry: {}",
+                user_id, guild_id, e
+            );
+            return;
+        }
+    }
+
+    if let Err(e) = MuteRepository::clear_lifted(db, guild_id, user_id, mute.unmute_at).await {
+        eprintln!(
+            "[WARN] Failed to clear mute for user {} in guild {}: {}",
+            user_id, guild_id, e
+        );
+    }
+}
+
+/// Lifts every mute that has already expired.
+async fn sweep(http: &Http, db: &DbPool) {
+    let due = match MuteRepository::due_before(db, Utc::now()).await {
+        Ok(due) => due,
+        Err(e) => {
+            eprintln!("[WARN] Failed to load expired mutes: {}", e);
+            return;
+        }
+    };
+
+    for mute in &due {
+        lift_if_expired(http, db, mute.guild_id as u64, mute.user_id as u64).await;
+    }
+}
+
+/// Restores mute handling after a restart and starts the periodic sweeper.
+///
+/// Mutes that expired while the bot was offline are lifted immediately; the
+/// rest get their timers re-armed.
+pub async fn start_mute_scheduler(http: Arc<Http>, db: DbPool) {
+    match MuteRepository::pending(&db).await {
+        Ok(mutes) => {
+            let (expired, running) = split_expired(mutes, Utc::now());
+            for mute in &expired {
+                lift(&http, &db, mute).await;
+            }
+            if !expired.is_empty() {
+                println!(
+                    "[OK] Lifted {} mutes that expired while offline",
+                    expired.len()
+                );
+            }
+
+            for mute in &running {
+                schedule_unmute(
+                    http.clone(),
+                    db.clone(),
+                    mute.guild_id as u64,
+                    mute.user_id as u64,
+                    mute.unmute_at,
+                );
+            }
+            if !running.is_empty() {
+                println!("[OK] Re-armed {} pending unmutes", running.len());
+            }
+        }
+        Err(e) => eprintln!("[WARN] Failed to load pending mutes: {}", e),
+    }
+
+    let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+    // The first tick completes immediately and the sweep above already ran
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        sweep(&http, &db).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mute(user_id: i64, unmute_at: DateTime<Utc>) -> Mute {
+        Mute {
+            guild_id: 1,
+            user_id,
+            role_id: 2,
+            unmute_at,
+        }
+    }
+
+    #[test]
+    fn expired_mutes_are_lifted_on_startup() {
+        let now = Utc::now();
+        let mutes = vec![
+            mute(1, now - chrono::Duration::hours(1)),
+            mute(2, now),
+            mute(3, now + chrono::Duration::minutes(5)),
+        ];
+
+        let (expired, running) = split_expired(mutes, now);
+
+        let expired: Vec<i64> = expired.iter().map(|m| m.user_id).collect();
+        let running: Vec<i64> = running.iter().map(|m| m.user_id).collect();
+        assert_eq!(expired, [1, 2]);
+        assert_eq!(running, [3]);
+    }
+}
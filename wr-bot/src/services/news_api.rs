@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CalendarEvent {
+    pub title: String,
+    #[serde(default)]
+    pub currency: String,
+    pub date_utc: DateTime<Utc>,
+    #[serde(default)]
+    pub impact: String,
+    pub forecast: Option<String>,
+    pub previous: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CalendarResponse {
+    #[serde(default)]
+    events: Vec<CalendarEvent>,
+}
+
+/// HTTP client for the news service REST API.
+pub struct NewsApiClient {
+    base_url: String,
+    http: reqwest::Client,
+}
+
+impl NewsApiClient {
+    pub fn new(base_url: &str) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            http,
+        }
+    }
+
+    /// Economic calendar events between `from` and `to`, earliest first.
+    ///
+    /// An error means the service could not be reached or answered badly; an
+    /// empty list means nothing is scheduled.
+    pub async fn get_calendar(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        currency: Option<&str>,
+    ) -> Result<Vec<CalendarEvent>, reqwest::Error> {
+        let mut query = vec![
+            ("date_from", from.to_rfc3339()),
+            ("date_to", to.to_rfc3339()),
+        ];
+        if let Some(currency) = currency {
+            query.push(("currencies", currency.to_uppercase()));
+        }
+
+        let response: CalendarResponse = self
+            .http
+            .get(format!("{}/api/v1/analytics/calendar", self.base_url))
+            .query(&query)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let mut events = response.events;
+        events.sort_by_key(|e| e.date_utc);
+        Ok(events)
+    }
+}
+
+static NEWS_API: OnceLock<NewsApiClient> = OnceLock::new();
+
+pub fn init_news_api(base_url: &str) {
+    let _ = NEWS_API.set(NewsApiClient::new(base_url));
+}
+
+pub fn get_news_api() -> Option<&'static NewsApiClient> {
+    NEWS_API.get()
+}
@@ -1,13 +1,78 @@
-use crate::repository::{CalendarRepository, DbPool, ForexRepository, StockRepository};
-use futures_util::{SinkExt, StreamExt};
-use poise::serenity_prelude::{ChannelId, CreateEmbed, CreateMessage, Http};
+use crate::repository::{
+    CalendarRepository, DbPool, ForexChannel, ForexRepository, StockChannel, StockRepository,
+};
+use crate::services::digest::{DigestItem, DigestKind, get_digest_buffer};
+use crate::services::stock_ws::StockNewsWsClient;
+use crate::services::ws_metrics::{MAX_FRAME_BYTES, WsMetrics, payload_preview};
+use crate::utils::branding::branded_embed;
+use crate::utils::failures::FailureTracker;
+use crate::utils::seen::SeenCache;
+use crate::utils::text::{
+    EMBED_FIELD_MAX, EMBED_FIELD_NAME_MAX, EMBED_TITLE_MAX, truncate_desc, truncate_field,
+};
+use crate::utils::threads;
+use crate::utils::webhook::{self, Delivery, FANOUT_CONCURRENCY};
+use futures_util::{SinkExt, StreamExt, stream};
+use parking_lot::Mutex;
+use poise::serenity_prelude::{ChannelId, CreateEmbed, CreateMessage, GuildId, Http};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
-use std::time::Duration;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
 const RECONNECT_DELAY_BASE: u64 = 5;
 const RECONNECT_DELAY_MAX: u64 = 300;
+const DEFAULT_MENTION_COOLDOWN_SECS: u64 = 300;
+const DEFAULT_DEDUP_WINDOW_SECS: u64 = 600;
+pub const DEFAULT_HEARTBEAT_INTERVAL_SECS: u64 = 30;
+
+static CONNECTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the news WebSocket currently has a live connection.
+pub fn is_connected() -> bool {
+    CONNECTED.load(Ordering::Relaxed)
+}
+
+/// Events this client acts on. Anything else is counted and skipped, even
+/// when its payload does not match [`NewsEventData`].
+const HANDLED_EVENTS: &[&str] = &[
+    "news.new",
+    "news.high_impact",
+    "stock.news.new",
+    "stock.news.high_impact",
+    "calendar.reminder",
+    "sentiment.alert",
+    "connected",
+    "subscribed",
+    "heartbeat",
+];
+
+/// Just the event name, used when the full message does not parse.
+#[derive(Deserialize)]
+struct EventEnvelope {
+    event: String,
+}
+
+enum ParsedMessage {
+    Event(Box<NewsEvent>),
+    /// An event type this bot does not handle yet; its payload may not fit
+    /// [`NewsEvent`], so it is skipped rather than counted as a parse error.
+    Unknown(String),
+}
+
+fn parse_message(text: &str) -> Result<ParsedMessage, serde_json::Error> {
+    match serde_json::from_str::<NewsEvent>(text) {
+        Ok(event) => Ok(ParsedMessage::Event(Box::new(event))),
+        Err(e) => match serde_json::from_str::<EventEnvelope>(text) {
+            Ok(envelope) if !HANDLED_EVENTS.contains(&envelope.event.as_str()) => {
+                Ok(ParsedMessage::Unknown(envelope.event))
+            }
+            _ => Err(e),
+        },
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewsEvent {
@@ -53,13 +118,33 @@ pub struct ArticleData {
     pub sentiment_confidence: Option<f64>,
     pub impact_level: Option<String>,
     pub impact_score: Option<i32>,
+    #[serde(default)]
     pub currency_pairs: Vec<String>,
+    #[serde(default)]
     pub currencies: Vec<String>,
+    /// Stock articles only.
+    #[serde(default)]
+    pub category: String,
+    /// Stock articles only.
+    #[serde(default)]
+    pub tickers: Vec<String>,
     pub published_at: Option<String>,
+    #[serde(default)]
     pub processed_at: String,
     pub image_url: Option<String>,
 }
 
+impl ArticleData {
+    /// Whether the sentiment confidence reaches `min`; articles without a
+    /// confidence always pass.
+    pub fn meets_confidence(&self, min: Option<f64>) -> bool {
+        match (self.sentiment_confidence, min) {
+            (Some(confidence), Some(min)) => confidence >= min,
+            _ => true,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiscordEmbed {
     pub title: Option<String>,
@@ -95,6 +180,17 @@ pub struct NewsWebSocketService {
     http: Arc<Http>,
     ws_url: String,
     bot_id: String,
+    mention_cooldown: Duration,
+    /// Time between heartbeats; twice this without any server message is
+    /// treated as a dead connection.
+    heartbeat_interval: Duration,
+    /// Last time each channel got an `@everyone` for high impact news.
+    last_mention: Mutex<HashMap<ChannelId, Instant>>,
+    metrics: WsMetrics,
+    /// Articles delivered recently, so reconnect replays skip the database
+    recently_sent: SeenCache,
+    /// Consecutive unreachable sends per channel, to disable dead subscriptions
+    failures: FailureTracker,
 }
 
 impl NewsWebSocketService {
@@ -104,6 +200,73 @@ impl NewsWebSocketService {
             http,
             ws_url,
             bot_id,
+            mention_cooldown: Duration::from_secs(DEFAULT_MENTION_COOLDOWN_SECS),
+            heartbeat_interval: Duration::from_secs(DEFAULT_HEARTBEAT_INTERVAL_SECS),
+            last_mention: Mutex::new(HashMap::new()),
+            metrics: WsMetrics::default(),
+            recently_sent: SeenCache::new(Duration::from_secs(DEFAULT_DEDUP_WINDOW_SECS)),
+            failures: FailureTracker::default(),
+        }
+    }
+
+    pub fn with_mention_cooldown(mut self, cooldown: Duration) -> Self {
+        self.mention_cooldown = cooldown;
+        self
+    }
+
+    pub fn with_heartbeat_interval(mut self, interval: Duration) -> Self {
+        // tokio intervals panic on zero
+        self.heartbeat_interval = interval.max(Duration::from_secs(1));
+        self
+    }
+
+    pub fn with_dedup_window(mut self, window: Duration) -> Self {
+        self.recently_sent = SeenCache::new(window);
+        self
+    }
+
+    pub fn with_failure_threshold(mut self, threshold: u32) -> Self {
+        self.failures = FailureTracker::new(threshold);
+        self
+    }
+
+    /// Returns true if the article is a duplicate, recording which layer caught it.
+    async fn is_duplicate(
+        &self,
+        key: &str,
+        sent_in_db: impl Future<Output = Result<bool, sqlx::Error>>,
+    ) -> Result<bool, sqlx::Error> {
+        if self.recently_sent.contains(key) {
+            self.metrics.record_duplicate_cached();
+            return Ok(true);
+        }
+        if sent_in_db.await? {
+            self.metrics.record_duplicate_db();
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Clears the recently-sent cache; the sent-news table still prevents duplicates.
+    pub fn clear_dedup_cache(&self) -> usize {
+        self.recently_sent.clear()
+    }
+
+    pub fn metrics(&self) -> &WsMetrics {
+        &self.metrics
+    }
+
+    /// Returns true if `channel_id` may be pinged now, recording the ping.
+    fn try_claim_mention(&self, channel_id: ChannelId) -> bool {
+        let now = Instant::now();
+        let mut last_mention = self.last_mention.lock();
+
+        match last_mention.get(&channel_id) {
+            Some(last) if now.duration_since(*last) < self.mention_cooldown => false,
+            _ => {
+                last_mention.insert(channel_id, now);
+                true
+            }
         }
     }
 
@@ -113,7 +276,10 @@ impl NewsWebSocketService {
         let mut reconnect_delay = RECONNECT_DELAY_BASE;
 
         loop {
-            match self.connect_and_listen().await {
+            let result = self.connect_and_listen().await;
+            CONNECTED.store(false, Ordering::Relaxed);
+
+            match result {
                 Ok(_) => {
                     println!("[NEWS-WS] Connection closed normally");
                     reconnect_delay = RECONNECT_DELAY_BASE;
@@ -124,6 +290,7 @@ impl NewsWebSocketService {
             }
 
             println!("[NEWS-WS] Reconnecting in {} seconds...", reconnect_delay);
+            self.metrics.record_reconnect();
             tokio::time::sleep(Duration::from_secs(reconnect_delay)).await;
 
             reconnect_delay = (reconnect_delay * 2).min(RECONNECT_DELAY_MAX);
@@ -142,22 +309,48 @@ impl NewsWebSocketService {
         let (mut write, mut read) = ws_stream.split();
 
         println!("[OK] News WebSocket connected!");
+        CONNECTED.store(true, Ordering::Relaxed);
 
-        let mut heartbeat_interval = tokio::time::interval(Duration::from_secs(30));
+        let mut heartbeat_interval = tokio::time::interval(self.heartbeat_interval);
+        let pong_timeout = self.heartbeat_interval * 2;
+        let mut last_message_at = Instant::now();
+        let mut last_heartbeat_at: Option<Instant> = None;
 
         loop {
             tokio::select! {
                 _ = heartbeat_interval.tick() => {
+                    // The server answers heartbeats, so silence after one means a
+                    // half-open connection
+                    if let Some(sent_at) = last_heartbeat_at
+                        && last_message_at < sent_at
+                        && last_message_at.elapsed() > pong_timeout
+                    {
+                        println!(
+                            "[NEWS-WS] No reply to heartbeat for {}s (timeout {}s), reconnecting",
+                            last_message_at.elapsed().as_secs(),
+                            pong_timeout.as_secs()
+                        );
+                        return Err(format!(
+                            "no message for {}s, forcing reconnect",
+                            last_message_at.elapsed().as_secs()
+                        )
+                        .into());
+                    }
+
                     let heartbeat = serde_json::json!({
                         "event": "heartbeat",
                         "data": {}
                     });
                     write.send(Message::Text(heartbeat.to_string())).await?;
+                    last_heartbeat_at = Some(Instant::now());
                 }
 
                 msg = read.next() => {
+                    last_message_at = Instant::now();
+
                     match msg {
                         Some(Ok(Message::Text(text))) => {
+                            self.metrics.record_message();
                             if let Err(e) = self.handle_message(&text).await {
                                 println!("[NEWS-WS] Error handling message: {}", e);
                             }
@@ -188,7 +381,29 @@ impl NewsWebSocketService {
         &self,
         text: &str,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let event: NewsEvent = serde_json::from_str(text)?;
+        if text.len() > MAX_FRAME_BYTES {
+            self.metrics.record_oversized_frame();
+            eprintln!(
+                "[NEWS-WS] Dropping {} byte frame, limit is {}",
+                text.len(),
+                MAX_FRAME_BYTES
+            );
+            return Ok(());
+        }
+
+        let event = match parse_message(text) {
+            Ok(ParsedMessage::Event(event)) => event,
+            Ok(ParsedMessage::Unknown(name)) => {
+                self.metrics.record_event(&name);
+                println!("[NEWS-WS] Unknown event: {}", name);
+                return Ok(());
+            }
+            Err(e) => {
+                self.metrics.record_parse_error();
+                return Err(format!("{} in payload: {}", e, payload_preview(text, 100)).into());
+            }
+        };
+        self.metrics.record_event(&event.event);
 
         match event.event.as_str() {
             "news.new" | "news.high_impact" => {
@@ -223,7 +438,14 @@ impl NewsWebSocketService {
         let discord_embed = data.discord_embed.as_ref().ok_or("No embed in event")?;
 
         // Check if already sent
-        if ForexRepository::is_news_sent(&self.db, &article.id).await? {
+        let dedup_key = format!("forex:{}", article.id);
+        if self
+            .is_duplicate(
+                &dedup_key,
+                ForexRepository::is_news_sent(&self.db, &article.id),
+            )
+            .await?
+        {
             return Ok(());
         }
 
@@ -238,10 +460,10 @@ impl NewsWebSocketService {
         let mut embed = CreateEmbed::new();
 
         if let Some(title) = &discord_embed.title {
-            embed = embed.title(title);
+            embed = embed.title(truncate_field(title, EMBED_TITLE_MAX));
         }
         if let Some(desc) = &discord_embed.description {
-            embed = embed.description(desc);
+            embed = embed.description(truncate_desc(desc));
         }
         if let Some(url) = &discord_embed.url {
             embed = embed.url(url);
@@ -251,7 +473,11 @@ impl NewsWebSocketService {
         }
         if let Some(fields) = &discord_embed.fields {
             for field in fields {
-                embed = embed.field(&field.name, &field.value, field.inline);
+                embed = embed.field(
+                    truncate_field(&field.name, EMBED_FIELD_NAME_MAX),
+                    truncate_field(&field.value, EMBED_FIELD_MAX),
+                    field.inline,
+                );
             }
         }
         if let Some(thumbnail) = &discord_embed.thumbnail {
@@ -266,28 +492,118 @@ impl NewsWebSocketService {
         let is_high_impact = event.event == "news.high_impact";
         let mention_everyone = data.mention_everyone.unwrap_or(false);
 
-        for channel in &channels {
-            let channel_id = ChannelId::new(channel.channel_id as u64);
+        let embed = &embed;
+        let sends: Vec<_> = channels
+            .iter()
+            .map(|channel| async move {
+                let channel_id = ChannelId::new(channel.channel_id as u64);
 
-            let mut message = CreateMessage::new().embed(embed.clone());
+                if !article.meets_confidence(channel.min_sentiment_confidence) {
+                    return None;
+                }
 
-            if is_high_impact && mention_everyone {
-                message = message.content("@everyone **HIGH IMPACT NEWS**");
-            }
+                if channel.digest
+                    && let Some(buffer) = get_digest_buffer()
+                {
+                    buffer.push(
+                        channel_id,
+                        DigestKind::Forex,
+                        channel.digest_interval_minutes,
+                        channel.webhook_url.clone(),
+                        DigestItem {
+                            title: article.title.clone(),
+                            url: article.original_url.clone(),
+                            source: article.source_name.clone(),
+                        },
+                    );
+                    return None;
+                }
 
-            if let Err(e) = channel_id.send_message(&self.http, message).await {
-                println!(
-                    "[NEWS-WS] Failed to send to channel {}: {}",
-                    channel.channel_id, e
-                );
-            }
-        }
+                // A configured role replaces @everyone; without one, the channel's setting
+                // overrides the event's flag
+                let mention = match channel.mention_role_id {
+                    Some(role_id) if is_high_impact => Some(format!("<@&{}>", role_id)),
+                    None if is_high_impact && channel.mention_everyone.unwrap_or(mention_everyone) => {
+                        Some("@everyone".to_string())
+                    }
+                    _ => None,
+                };
+
+                let content = mention.map(|mention| {
+                    // Still deliver the embed during a burst, just without the ping
+                    if self.try_claim_mention(channel_id) {
+                        format!("{} **HIGH IMPACT NEWS**", mention)
+                    } else {
+                        "**HIGH IMPACT NEWS**".to_string()
+                    }
+                });
+
+                let thread = if channel.use_thread {
+                    self.forex_thread(channel).await
+                } else {
+                    None
+                };
+
+                let result = webhook::send_embed_to(
+                    &self.http,
+                    channel_id,
+                    thread,
+                    channel.webhook_url.as_deref(),
+                    content.as_deref(),
+                    embed.clone(),
+                )
+                .await;
+                let delivered = result.is_ok();
+                match result {
+                    Ok(Delivery::WebhookGone) => {
+                        self.failures.record_success("forex", channel_id.get());
+                        println!(
+                            "[NEWS-WS] Webhook for channel {} is gone, clearing it",
+                            channel.channel_id
+                        );
+                        if let Err(e) =
+                            ForexRepository::clear_webhook_for_channel(&self.db, channel_id.get()).await
+                        {
+                            println!("[NEWS-WS] Failed to clear webhook: {}", e);
+                        }
+                    }
+                    Ok(_) => self.failures.record_success("forex", channel_id.get()),
+                    Err(e) => {
+                        println!(
+                            "[NEWS-WS] Failed to send to channel {}: {}",
+                            channel.channel_id, e
+                        );
+                        if self.failures.record_failure("forex", channel_id.get(), &e) {
+                            println!(
+                                "[NEWS-WS] Disabling forex news for channel {} after repeated failures",
+                                channel.channel_id
+                            );
+                            if let Err(e) =
+                                ForexRepository::disable_channel(&self.db, channel.guild_id as u64).await
+                            {
+                                println!("[NEWS-WS] Failed to disable channel: {}", e);
+                            }
+                        }
+                    }
+                }
+
+                Some(delivered)
+            })
+            .collect();
+        let results: Vec<Option<bool>> = stream::iter(sends)
+            .buffer_unordered(FANOUT_CONCURRENCY)
+            .collect()
+            .await;
+        let delivered = results.iter().filter(|r| **r == Some(true)).count();
 
         // Mark as sent
         ForexRepository::insert_news(&self.db, &article.id, &article.source_name).await?;
+        self.recently_sent.insert(dedup_key);
+        self.metrics.record_delivered();
 
         println!(
-            "[NEWS-WS] Sent news to {} channels: {}",
+            "[NEWS-WS] Sent news to {}/{} channels: {}",
+            delivered,
             channels.len(),
             article.title
         );
@@ -295,6 +611,37 @@ impl NewsWebSocketService {
         Ok(())
     }
 
+    /// Resolves the forex news thread for `channel`, storing a newly created one.
+    async fn forex_thread(&self, channel: &ForexChannel) -> Option<ChannelId> {
+        let parent = ChannelId::new(channel.channel_id as u64);
+        let thread =
+            threads::news_thread(&self.http, parent, channel.thread_id, "Forex News").await?;
+        if thread.created
+            && let Err(e) = ForexRepository::set_thread(
+                &self.db,
+                channel.guild_id as u64,
+                Some(thread.id.get()),
+            )
+            .await
+        {
+            println!("[NEWS-WS] Failed to store news thread: {}", e);
+        }
+        Some(thread.id)
+    }
+
+    async fn stock_thread(&self, channel: &StockChannel) -> Option<ChannelId> {
+        let parent = ChannelId::new(channel.channel_id as u64);
+        let thread =
+            threads::news_thread(&self.http, parent, channel.thread_id, "Berita Saham").await?;
+        if thread.created
+            && let Err(e) =
+                StockRepository::set_thread(&self.db, parent.get(), Some(thread.id.get())).await
+        {
+            println!("[STOCK-WS] Failed to store news thread: {}", e);
+        }
+        Some(thread.id)
+    }
+
     async fn handle_stock_news_event(
         &self,
         event: &NewsEvent,
@@ -303,11 +650,29 @@ impl NewsWebSocketService {
         let article = data.article.as_ref().ok_or("No article in event")?;
         let discord_embed = data.discord_embed.as_ref().ok_or("No embed in event")?;
 
-        if StockRepository::is_stock_news_sent(&self.db, &article.id).await? {
+        let dedup_key = format!("stock:{}", article.id);
+        if self
+            .is_duplicate(
+                &dedup_key,
+                StockRepository::is_stock_news_sent(&self.db, &article.id),
+            )
+            .await?
+        {
             return Ok(());
         }
 
-        let channels = StockRepository::get_active_channels(&self.db).await?;
+        let channels: Vec<_> = StockRepository::get_active_channels(&self.db)
+            .await?
+            .into_iter()
+            .filter(|ch| {
+                StockNewsWsClient::passes_filters(
+                    ch,
+                    &article.tickers,
+                    &article.category,
+                    article.impact_level.as_deref(),
+                )
+            })
+            .collect();
 
         if channels.is_empty() {
             return Ok(());
@@ -316,10 +681,10 @@ impl NewsWebSocketService {
         let mut embed = CreateEmbed::new();
 
         if let Some(title) = &discord_embed.title {
-            embed = embed.title(title);
+            embed = embed.title(truncate_field(title, EMBED_TITLE_MAX));
         }
         if let Some(desc) = &discord_embed.description {
-            embed = embed.description(desc);
+            embed = embed.description(truncate_desc(desc));
         }
         if let Some(url) = &discord_embed.url {
             embed = embed.url(url);
@@ -329,7 +694,11 @@ impl NewsWebSocketService {
         }
         if let Some(fields) = &discord_embed.fields {
             for field in fields {
-                embed = embed.field(&field.name, &field.value, field.inline);
+                embed = embed.field(
+                    truncate_field(&field.name, EMBED_FIELD_NAME_MAX),
+                    truncate_field(&field.value, EMBED_FIELD_MAX),
+                    field.inline,
+                );
             }
         }
         if let Some(thumbnail) = &discord_embed.thumbnail {
@@ -343,27 +712,104 @@ impl NewsWebSocketService {
 
         let is_high_impact = event.event == "stock.news.high_impact";
 
-        for channel in &channels {
-            let channel_id = ChannelId::new(channel.channel_id as u64);
-
-            let mut message = CreateMessage::new().embed(embed.clone());
+        let embed = &embed;
+        let sends: Vec<_> = channels
+            .iter()
+            .map(|channel| async move {
+                let channel_id = ChannelId::new(channel.channel_id as u64);
+
+                if channel.digest
+                    && let Some(buffer) = get_digest_buffer()
+                {
+                    buffer.push(
+                        channel_id,
+                        DigestKind::Stock,
+                        channel.digest_interval_minutes,
+                        channel.webhook_url.clone(),
+                        DigestItem {
+                            title: article.title.clone(),
+                            url: article.original_url.clone(),
+                            source: article.source_name.clone(),
+                        },
+                    );
+                    return None;
+                }
 
-            if is_high_impact && channel.mention_everyone {
-                message = message.content("@everyone **BERITA SAHAM PENTING**");
-            }
+                let content = if is_high_impact {
+                    match (channel.mention_role_id, channel.mention_everyone) {
+                        (Some(role_id), _) => Some(format!("<@&{}> **BERITA SAHAM PENTING**", role_id)),
+                        (None, true) => Some("@everyone **BERITA SAHAM PENTING**".to_string()),
+                        (None, false) => None,
+                    }
+                } else {
+                    None
+                };
+
+                let thread = if channel.use_thread {
+                    self.stock_thread(channel).await
+                } else {
+                    None
+                };
+
+                let result = webhook::send_embed_to(
+                    &self.http,
+                    channel_id,
+                    thread,
+                    channel.webhook_url.as_deref(),
+                    content.as_deref(),
+                    embed.clone(),
+                )
+                .await;
+                let delivered = result.is_ok();
+                match result {
+                    Ok(Delivery::WebhookGone) => {
+                        self.failures.record_success("stock", channel_id.get());
+                        println!(
+                            "[STOCK-WS] Webhook for channel {} is gone, clearing it",
+                            channel.channel_id
+                        );
+                        if let Err(e) =
+                            StockRepository::set_webhook(&self.db, channel_id.get(), None).await
+                        {
+                            println!("[STOCK-WS] Failed to clear webhook: {}", e);
+                        }
+                    }
+                    Ok(_) => self.failures.record_success("stock", channel_id.get()),
+                    Err(e) => {
+                        println!(
+                            "[STOCK-WS] Failed to send to channel {}: {}",
+                            channel.channel_id, e
+                        );
+                        if self.failures.record_failure("stock", channel_id.get(), &e) {
+                            println!(
+                                "[STOCK-WS] Disabling stock news for channel {} after repeated failures",
+                                channel.channel_id
+                            );
+                            if let Err(e) =
+                                StockRepository::disable_channel(&self.db, channel_id.get()).await
+                            {
+                                println!("[STOCK-WS] Failed to disable channel: {}", e);
+                            }
+                        }
+                    }
+                }
 
-            if let Err(e) = channel_id.send_message(&self.http, message).await {
-                println!(
-                    "[STOCK-WS] Failed to send to channel {}: {}",
-                    channel.channel_id, e
-                );
-            }
-        }
+                Some(delivered)
+            })
+            .collect();
+        let results: Vec<Option<bool>> = stream::iter(sends)
+            .buffer_unordered(FANOUT_CONCURRENCY)
+            .collect()
+            .await;
+        let delivered = results.iter().filter(|r| **r == Some(true)).count();
 
         StockRepository::insert_stock_news(&self.db, &article.id, &article.source_name).await?;
+        self.recently_sent.insert(dedup_key);
+        self.metrics.record_delivered();
 
         println!(
-            "[STOCK-WS] Sent stock news to {} channels: {}",
+            "[STOCK-WS] Sent stock news to {}/{} channels: {}",
+            delivered,
             channels.len(),
             article.title
         );
@@ -393,10 +839,10 @@ impl NewsWebSocketService {
 
         let embed = CreateEmbed::new()
             .title("CALENDAR REMINDER")
-            .description(format!(
+            .description(truncate_desc(&format!(
                 "**{} - {}**",
                 calendar_event.currency, calendar_event.title
-            ))
+            )))
             .field("Waktu", &calendar_event.date_wib, true)
             .field("Forecast", &calendar_event.forecast, true)
             .field("Previous", &calendar_event.previous, true)
@@ -415,17 +861,37 @@ impl NewsWebSocketService {
         for channel in &channels {
             let channel_id = ChannelId::new(channel.channel_id as u64);
 
-            let mut message = CreateMessage::new().embed(embed.clone());
+            let guild_id = GuildId::new(channel.guild_id as u64);
+            let embed = branded_embed(&self.db, Some(guild_id), embed.clone()).await;
+            let mut message = CreateMessage::new().embed(embed);
 
             if channel.mention_everyone {
                 message = message.content("@everyone **HIGH IMPACT EVENT**");
             }
 
-            if let Err(e) = channel_id.send_message(&self.http, message).await {
-                println!(
-                    "[CALENDAR-WS] Failed to send to channel {}: {}",
-                    channel.channel_id, e
-                );
+            match channel_id.send_message(&self.http, message).await {
+                Ok(_) => self.failures.record_success("calendar", channel_id.get()),
+                Err(e) => {
+                    println!(
+                        "[CALENDAR-WS] Failed to send to channel {}: {}",
+                        channel.channel_id, e
+                    );
+                    if self
+                        .failures
+                        .record_failure("calendar", channel_id.get(), &e)
+                    {
+                        println!(
+                            "[CALENDAR-WS] Disabling reminders for channel {} after repeated failures",
+                            channel.channel_id
+                        );
+                        if let Err(e) =
+                            CalendarRepository::disable_channel(&self.db, channel.guild_id as u64)
+                                .await
+                        {
+                            println!("[CALENDAR-WS] Failed to disable channel: {}", e);
+                        }
+                    }
+                }
             }
         }
 
@@ -442,9 +908,73 @@ impl NewsWebSocketService {
     }
 }
 
-pub fn start_news_ws_service(db: DbPool, http: Arc<Http>, ws_url: String, bot_id: String) {
-    let service = Arc::new(NewsWebSocketService::new(db, http, ws_url, bot_id));
+static NEWS_WS_SERVICE: OnceLock<Arc<NewsWebSocketService>> = OnceLock::new();
+
+pub fn get_news_ws_service() -> Option<Arc<NewsWebSocketService>> {
+    NEWS_WS_SERVICE.get().cloned()
+}
+
+pub fn start_news_ws_service(service: NewsWebSocketService) {
+    let service = Arc::new(service);
+    let _ = NEWS_WS_SERVICE.set(service.clone());
     tokio::spawn(async move {
         service.start().await;
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article(confidence: Option<f64>) -> ArticleData {
+        serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "title": "Fed holds rates",
+            "source_name": "Reuters",
+            "original_url": "https://example.com/1",
+            "sentiment_confidence": confidence,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn low_confidence_articles_are_dropped() {
+        assert!(!article(Some(0.3)).meets_confidence(Some(0.5)));
+        assert!(article(Some(0.5)).meets_confidence(Some(0.5)));
+    }
+
+    #[test]
+    fn missing_minimum_or_confidence_passes() {
+        assert!(article(Some(0.3)).meets_confidence(None));
+        assert!(article(None).meets_confidence(Some(0.5)));
+    }
+
+    #[test]
+    fn unknown_event_with_unexpected_data_is_skipped() {
+        let text = r#"{"event":"market.tick","data":{"article":42,"price":"1.0850"}}"#;
+        match parse_message(text) {
+            Ok(ParsedMessage::Unknown(name)) => assert_eq!(name, "market.tick"),
+            _ => panic!("expected an unknown event"),
+        }
+    }
+
+    #[test]
+    fn handled_event_with_unexpected_data_is_an_error() {
+        let text = r#"{"event":"news.new","data":{"article":42}}"#;
+        assert!(parse_message(text).is_err());
+    }
+
+    #[test]
+    fn article_without_pairs_or_processed_at_parses() {
+        let text = r#"{"event":"news.new","data":{"article":{
+            "id":"7","title":"ECB speaks","source_name":"FXStreet",
+            "original_url":"https://example.com/7"
+        }}}"#;
+        let Ok(ParsedMessage::Event(event)) = parse_message(text) else {
+            panic!("expected a news event");
+        };
+        let article = event.data.unwrap().article.unwrap();
+        assert!(article.currency_pairs.is_empty());
+        assert!(article.processed_at.is_empty());
+    }
+}
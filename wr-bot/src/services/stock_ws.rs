@@ -1,10 +1,41 @@
-use futures_util::{SinkExt, StreamExt};
+use futures_util::{SinkExt, StreamExt, stream};
 use serde::Deserialize;
-use serenity::all::{ChannelId, CreateEmbed, CreateEmbedFooter, CreateMessage, Http};
+use serenity::all::{ChannelId, CreateEmbed, CreateEmbedFooter, Http};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
 
 use crate::error::BotError;
+use crate::repository::{EmbedStyleRepository, StockChannel, StockEmbedStyle, StockRepository};
+use crate::services::digest::{DigestItem, DigestKind, get_digest_buffer};
+use crate::services::ws_metrics::{MAX_FRAME_BYTES, WsMetrics, payload_preview};
+use crate::utils::failures::FailureTracker;
+use crate::utils::text::{EMBED_TITLE_MAX, truncate_desc, truncate_field};
+use crate::utils::threads;
+use crate::utils::webhook::{self, Delivery, FANOUT_CONCURRENCY};
+
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+const SILENCE_TIMEOUT: Duration = Duration::from_secs(90);
+
+static CONNECTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the stock news WebSocket currently has a live connection.
+pub fn is_connected() -> bool {
+    CONNECTED.load(Ordering::Relaxed)
+}
+
+/// JSON text carried by a data frame; `None` for control frames.
+///
+/// Some servers send JSON as binary frames under load, so those are decoded
+/// as UTF-8. Fragmented messages are already reassembled by tungstenite.
+fn frame_text(frame: &WsMessage) -> Option<Result<&str, std::str::Utf8Error>> {
+    match frame {
+        WsMessage::Text(text) => Some(Ok(text.as_str())),
+        WsMessage::Binary(data) => Some(std::str::from_utf8(data)),
+        _ => None,
+    }
+}
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct StockNewsData {
@@ -15,11 +46,14 @@ pub struct StockNewsData {
     pub source_name: String,
     pub source_url: String,
     pub original_url: String,
+    #[serde(default)]
     pub category: String,
+    #[serde(default)]
     pub tickers: Vec<String>,
     pub sentiment: Option<String>,
     pub impact_level: Option<String>,
     pub published_at: Option<String>,
+    #[serde(default)]
     pub processed_at: String,
 }
 
@@ -33,6 +67,8 @@ pub struct StockNewsWsClient {
     ws_url: String,
     http: Option<Arc<Http>>,
     db_pool: Option<Arc<sqlx::PgPool>>,
+    metrics: WsMetrics,
+    failures: FailureTracker,
 }
 
 impl StockNewsWsClient {
@@ -41,14 +77,25 @@ impl StockNewsWsClient {
             ws_url: ws_url.to_string(),
             http: None,
             db_pool: None,
+            metrics: WsMetrics::default(),
+            failures: FailureTracker::default(),
         }
     }
 
+    pub fn metrics(&self) -> &WsMetrics {
+        &self.metrics
+    }
+
     pub fn with_http(mut self, http: Arc<Http>) -> Self {
         self.http = Some(http);
         self
     }
 
+    pub fn with_failure_threshold(mut self, threshold: u32) -> Self {
+        self.failures = FailureTracker::new(threshold);
+        self
+    }
+
     pub fn with_db(mut self, pool: Arc<sqlx::PgPool>) -> Self {
         self.db_pool = Some(pool);
         self
@@ -63,6 +110,7 @@ impl StockNewsWsClient {
             match connect_async(&url).await {
                 Ok((ws_stream, _)) => {
                     println!("[STOCK-WS] Connected successfully");
+                    CONNECTED.store(true, Ordering::Relaxed);
                     
                     let (mut write, mut read) = ws_stream.split();
                     
@@ -75,25 +123,63 @@ impl StockNewsWsClient {
                         eprintln!("[STOCK-WS] Failed to subscribe: {}", e);
                     }
                     
-                    while let Some(msg) = read.next().await {
-                        match msg {
-                            Ok(WsMessage::Text(text)) => {
-                                self.handle_message(&text).await;
-                            }
-                            Ok(WsMessage::Ping(data)) => {
-                                let _ = write.send(WsMessage::Pong(data)).await;
-                            }
-                            Ok(WsMessage::Close(_)) => {
-                                println!("[STOCK-WS] Server closed connection");
-                                break;
+                    let ping_msg = serde_json::json!({ "action": "ping" }).to_string();
+                    let mut ping_interval = tokio::time::interval(PING_INTERVAL);
+                    let mut last_message_at = Instant::now();
+
+                    loop {
+                        tokio::select! {
+                            _ = ping_interval.tick() => {
+                                // Catches half-open connections that never deliver a close frame
+                                if last_message_at.elapsed() > SILENCE_TIMEOUT {
+                                    eprintln!(
+                                        "[STOCK-WS] No message for {}s, forcing reconnect",
+                                        last_message_at.elapsed().as_secs()
+                                    );
+                                    break;
+                                }
+                                if let Err(e) = write.send(WsMessage::Text(ping_msg.clone())).await {
+                                    eprintln!("[STOCK-WS] Failed to send ping: {}", e);
+                                    break;
+                                }
                             }
-                            Err(e) => {
-                                eprintln!("[STOCK-WS] Error: {}", e);
-                                break;
+
+                            msg = read.next() => {
+                                last_message_at = Instant::now();
+
+                                match msg {
+                                    Some(Ok(WsMessage::Ping(data))) => {
+                                        let _ = write.send(WsMessage::Pong(data)).await;
+                                    }
+                                    Some(Ok(WsMessage::Close(_))) => {
+                                        println!("[STOCK-WS] Server closed connection");
+                                        break;
+                                    }
+                                    Some(Ok(frame)) => match frame_text(&frame) {
+                                        Some(Ok(text)) => {
+                                            self.metrics.record_message();
+                                            self.handle_message(text).await
+                                        }
+                                        Some(Err(e)) => {
+                                            self.metrics.record_parse_error();
+                                            eprintln!(
+                                                "[STOCK-WS] Dropping binary frame ({} bytes) that is not UTF-8: {}",
+                                                frame.len(),
+                                                e
+                                            );
+                                        }
+                                        None => {}
+                                    },
+                                    Some(Err(e)) => {
+                                        eprintln!("[STOCK-WS] Error: {}", e);
+                                        break;
+                                    }
+                                    None => break,
+                                }
                             }
-                            _ => {}
                         }
                     }
+                    CONNECTED.store(false, Ordering::Relaxed);
                 }
                 Err(e) => {
                     eprintln!("[STOCK-WS] Connection failed: {}", e);
@@ -101,59 +187,240 @@ impl StockNewsWsClient {
             }
             
             println!("[STOCK-WS] Reconnecting in 10 seconds...");
+            self.metrics.record_reconnect();
             tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
         }
     }
 
     async fn handle_message(&self, text: &str) {
-        if let Ok(event) = serde_json::from_str::<StockNewsEvent>(text) {
-            match event.event.as_str() {
-                "stock.new" | "stock.high_impact" => {
-                    println!("[STOCK-WS] Received stock news: {}", event.data.title);
-                    if let (Some(http), Some(pool)) = (&self.http, &self.db_pool) {
-                        self.broadcast_stock_news(&event.data, event.event.as_str(), http, pool).await;
-                    }
+        if text.len() > MAX_FRAME_BYTES {
+            self.metrics.record_oversized_frame();
+            eprintln!(
+                "[STOCK-WS] Dropping {} byte frame, limit is {}",
+                text.len(),
+                MAX_FRAME_BYTES
+            );
+            return;
+        }
+
+        let event = match serde_json::from_str::<StockNewsEvent>(text) {
+            Ok(event) => event,
+            Err(e) => {
+                // Control messages (subscribe acks, heartbeats) carry no `data`
+                if serde_json::from_str::<serde_json::Value>(text).is_err() {
+                    self.metrics.record_parse_error();
+                    eprintln!(
+                        "[STOCK-WS] Dropping message that is not valid JSON: {} ({})",
+                        e,
+                        payload_preview(text, 100)
+                    );
                 }
-                _ => {}
+                return;
             }
+        };
+
+        self.metrics.record_event(&event.event);
+
+        match event.event.as_str() {
+            "stock.new" | "stock.high_impact" => {
+                println!("[STOCK-WS] Received stock news: {}", event.data.title);
+                if let (Some(http), Some(pool)) = (&self.http, &self.db_pool) {
+                    self.broadcast_stock_news(&event.data, event.event.as_str(), http, pool)
+                        .await;
+                }
+            }
+            _ => {}
         }
     }
 
     async fn broadcast_stock_news(&self, data: &StockNewsData, event_type: &str, http: &Arc<Http>, pool: &Arc<sqlx::PgPool>) {
-        let channels: Vec<(i64, bool)> = match sqlx::query_as(
-            "SELECT channel_id, mention_everyone FROM stock_news_channels WHERE is_active = TRUE"
-        )
-        .fetch_all(pool.as_ref())
-        .await {
+        let channels = match StockRepository::get_active_channels(pool).await {
             Ok(c) => c,
             Err(e) => {
                 eprintln!("[STOCK-WS] Failed to get channels: {}", e);
                 return;
             }
         };
+        let channels: Vec<_> = channels
+            .into_iter()
+            .filter(|ch| {
+                Self::passes_filters(
+                    ch,
+                    &data.tickers,
+                    &data.category,
+                    data.impact_level.as_deref(),
+                )
+            })
+            .collect();
 
         if channels.is_empty() {
             return;
         }
 
-        let embed = self.build_stock_embed(data);
+        let guild_ids: Vec<i64> = channels.iter().map(|ch| ch.guild_id).collect();
+        let styles = EmbedStyleRepository::get_for_guilds(pool, &guild_ids)
+            .await
+            .unwrap_or_else(|e| {
+                eprintln!("[STOCK-WS] Failed to load embed styles: {}", e);
+                Default::default()
+            });
+        let styles = &styles;
+        let default_embed = &Self::build_stock_embed(data, None);
         
-        for (channel_id, mention_everyone) in &channels {
-            let channel = ChannelId::new(*channel_id as u64);
-            
-            let mut message = CreateMessage::new().embed(embed.clone());
+        let sends: Vec<_> = channels
+            .iter()
+            .map(|ch| async move {
+                let channel = ChannelId::new(ch.channel_id as u64);
+                let embed = match styles.get(&ch.guild_id) {
+                    Some(style) => Self::build_stock_embed(data, Some(style)),
+                    None => default_embed.clone(),
+                };
+
+                if ch.digest
+                    && let Some(buffer) = get_digest_buffer()
+                {
+                    buffer.push(
+                        channel,
+                        DigestKind::Stock,
+                        ch.digest_interval_minutes,
+                        ch.webhook_url.clone(),
+                        DigestItem {
+                            title: data.title.clone(),
+                            url: data.original_url.clone(),
+                            source: data.source_name.clone(),
+                        },
+                    );
+                    return None;
+                }
             
-            if event_type == "stock.high_impact" && *mention_everyone {
-                message = message.content("@everyone **HIGH IMPACT STOCK NEWS**");
+                let content = if event_type == "stock.high_impact" {
+                    match (ch.mention_role_id, ch.mention_everyone) {
+                        (Some(role_id), _) => {
+                            Some(format!("<@&{}> **HIGH IMPACT STOCK NEWS**", role_id))
+                        }
+                        (None, true) => Some("@everyone **HIGH IMPACT STOCK NEWS**".to_string()),
+                        (None, false) => None,
+                    }
+                } else {
+                    None
+                };
+
+                let mut thread = None;
+                if ch.use_thread
+                    && let Some(t) =
+                        threads::news_thread(http, channel, ch.thread_id, "Berita Saham").await
+                {
+                    if t.created
+                        && let Err(e) =
+                            StockRepository::set_thread(pool, channel.get(), Some(t.id.get())).await
+                    {
+                        eprintln!("[STOCK-WS] Failed to store news thread: {}", e);
+                    }
+                    thread = Some(t.id);
+                }
+
+                let result = webhook::send_embed_to(
+                    http,
+                    channel,
+                    thread,
+                    ch.webhook_url.as_deref(),
+                    content.as_deref(),
+                    embed,
+                )
+                .await;
+                let delivered = result.is_ok();
+                match result {
+                    Ok(Delivery::WebhookGone) => {
+                        self.failures.record_success("stock", channel.get());
+                        eprintln!(
+                            "[STOCK-WS] Webhook for channel {} is gone, clearing it",
+                            ch.channel_id
+                        );
+                        if let Err(e) =
+                            StockRepository::set_webhook(pool, channel.get(), None).await
+                        {
+                            eprintln!("[STOCK-WS] Failed to clear webhook: {}", e);
+                        }
+                    }
+                    Ok(_) => self.failures.record_success("stock", channel.get()),
+                    Err(e) => {
+                        eprintln!(
+                            "[STOCK-WS] Failed to send to channel {}: {}",
+                            ch.channel_id, e
+                        );
+                        if self.failures.record_failure("stock", channel.get(), &e) {
+                            eprintln!(
+                                "[STOCK-WS] Disabling channel {} after repeated failures",
+                                ch.channel_id
+                            );
+                            if let Err(e) =
+                                StockRepository::disable_channel(pool, channel.get()).await
+                            {
+                                eprintln!("[STOCK-WS] Failed to disable channel: {}", e);
+                            }
+                        }
+                    }
+                }
+
+                Some(delivered)
+            })
+            .collect();
+        let results: Vec<Option<bool>> = stream::iter(sends)
+            .buffer_unordered(FANOUT_CONCURRENCY)
+            .collect()
+            .await;
+        let delivered = results.iter().filter(|r| **r == Some(true)).count();
+        let failed = results.iter().filter(|r| **r == Some(false)).count();
+        if failed > 0 {
+            eprintln!(
+                "[STOCK-WS] Delivered to {} channels, {} failed",
+                delivered, failed
+            );
+        }
+    }
+
+    /// Whether an article with these tickers, category and impact passes a
+    /// channel's filters.
+    pub fn passes_filters(
+        channel: &StockChannel,
+        tickers: &[String],
+        category: &str,
+        impact_level: Option<&str>,
+    ) -> bool {
+        fn impact_rank(level: &str) -> u8 {
+            match level {
+                "high" => 3,
+                "medium" => 2,
+                "low" => 1,
+                _ => 0,
             }
-            
-            if let Err(e) = channel.send_message(http, message).await {
-                eprintln!("[STOCK-WS] Failed to send to channel {}: {}", channel_id, e);
+        }
+
+        fn matches_list(filter: Option<&str>, mut pred: impl FnMut(&str) -> bool) -> bool {
+            match filter.map(str::trim).filter(|f| !f.is_empty()) {
+                Some(f) => f.split(',').map(str::trim).any(&mut pred),
+                None => true,
             }
         }
+
+        let tickers_ok = matches_list(channel.tickers_filter.as_deref(), |t| {
+            tickers.iter().any(|ticker| ticker.eq_ignore_ascii_case(t))
+        });
+        let categories_ok = matches_list(channel.categories.as_deref(), |c| {
+            category.eq_ignore_ascii_case(c)
+        });
+        let impact_ok = channel
+            .min_impact
+            .as_deref()
+            .is_none_or(|min| impact_rank(impact_level.unwrap_or_default()) >= impact_rank(min));
+
+        tickers_ok && categories_ok && impact_ok
     }
 
-    fn build_stock_embed(&self, data: &StockNewsData) -> CreateEmbed {
+    /// Embed for one article, using the guild's markers when `style` is given.
+    pub fn build_stock_embed(data: &StockNewsData, style: Option<&StockEmbedStyle>) -> CreateEmbed {
+        let default_style = StockEmbedStyle::default();
+        let style = style.unwrap_or(&default_style);
 
         let color = match data.sentiment.as_deref() {
             Some("bullish") => 0x00FF00,
@@ -161,12 +428,11 @@ impl StockNewsWsClient {
             _ => 0x2962FF, 
         };
 
-        let impact_bar = match data.impact_level.as_deref() {
-            Some("high") => "HIGH",
-            Some("medium") => "MED",
-            Some("low") => "LOW",
-            _ => "-",
-        };
+        let impact_bar = style.impact_label(data.impact_level.as_deref());
+        let sentiment_prefix = style
+            .sentiment_marker(data.sentiment.as_deref())
+            .map(|marker| format!("{} ", marker))
+            .unwrap_or_default();
 
         let category_label = match data.category.as_str() {
             "market" => "MARKET",
@@ -189,8 +455,11 @@ impl StockNewsWsClient {
             .unwrap_or_default();
 
         let mut embed = CreateEmbed::new()
-            .title(format!("{}{}", category_label, tickers_str))
-            .description(&data.title)
+            .title(truncate_field(
+                &format!("{}{}{}", sentiment_prefix, category_label, tickers_str),
+                EMBED_TITLE_MAX,
+            ))
+            .description(truncate_desc(&data.title))
             .color(color)
             .footer(CreateEmbedFooter::new(format!(
                 "Stock Alert | {} | {}",
@@ -213,11 +482,48 @@ use tokio::sync::RwLock;
 
 static STOCK_WS_CLIENT: OnceLock<RwLock<Option<Arc<StockNewsWsClient>>>> = OnceLock::new();
 
-pub fn init_stock_ws_client(ws_url: &str, http: Arc<Http>, pool: Arc<sqlx::PgPool>) {
-    let client = Arc::new(StockNewsWsClient::new(ws_url).with_http(http).with_db(pool));
+pub fn init_stock_ws_client(
+    ws_url: &str,
+    http: Arc<Http>,
+    pool: Arc<sqlx::PgPool>,
+    failure_threshold: u32,
+) {
+    let client = Arc::new(
+        StockNewsWsClient::new(ws_url)
+            .with_http(http)
+            .with_db(pool)
+            .with_failure_threshold(failure_threshold),
+    );
     let _ = STOCK_WS_CLIENT.set(RwLock::new(Some(client)));
 }
 
 pub async fn get_stock_ws_client_async() -> Option<Arc<StockNewsWsClient>> {
     STOCK_WS_CLIENT.get()?.read().await.clone()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_frames_are_decoded_as_text() {
+        let json = r#"{"event":"stock.new"}"#;
+        assert_eq!(frame_text(&WsMessage::Text(json.into())), Some(Ok(json)));
+        assert_eq!(
+            frame_text(&WsMessage::Binary(json.as_bytes().to_vec())),
+            Some(Ok(json))
+        );
+    }
+
+    #[test]
+    fn non_utf8_binary_frames_are_rejected() {
+        let frame = WsMessage::Binary(vec![b'{', 0xff, b'}']);
+        assert!(matches!(frame_text(&frame), Some(Err(_))));
+    }
+
+    #[test]
+    fn control_frames_carry_no_text() {
+        assert_eq!(frame_text(&WsMessage::Ping(vec![1, 2])), None);
+        assert_eq!(frame_text(&WsMessage::Pong(Vec::new())), None);
+    }
+}
@@ -0,0 +1,108 @@
+use parking_lot::Mutex;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Frames larger than this are dropped without being parsed.
+pub const MAX_FRAME_BYTES: usize = 256 * 1024;
+
+/// Leading part of a payload for log lines, cut at a char boundary so
+/// multi-byte UTF-8 never panics.
+pub fn payload_preview(text: &str, max_bytes: usize) -> &str {
+    let mut end = max_bytes.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// Cumulative WebSocket stream counters since process start.
+#[derive(Debug, Default)]
+pub struct WsMetrics {
+    messages_received: AtomicU64,
+    reconnects: AtomicU64,
+    parse_errors: AtomicU64,
+    oversized_frames: AtomicU64,
+    events: Mutex<BTreeMap<String, u64>>,
+    /// Duplicates dropped by the in-memory recently-seen cache
+    duplicates_cached: AtomicU64,
+    /// Duplicates dropped by the sent-news table
+    duplicates_db: AtomicU64,
+    delivered: AtomicU64,
+}
+
+#[derive(Debug, Clone)]
+pub struct WsMetricsSnapshot {
+    pub messages_received: u64,
+    pub reconnects: u64,
+    pub parse_errors: u64,
+    pub oversized_frames: u64,
+    pub events: BTreeMap<String, u64>,
+    pub duplicates_cached: u64,
+    pub duplicates_db: u64,
+    pub delivered: u64,
+}
+
+impl WsMetrics {
+    pub fn record_message(&self) {
+        self.messages_received.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_reconnect(&self) {
+        self.reconnects.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_parse_error(&self) {
+        self.parse_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_oversized_frame(&self) {
+        self.oversized_frames.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_event(&self, event: &str) {
+        *self.events.lock().entry(event.to_string()).or_insert(0) += 1;
+    }
+
+    pub fn record_duplicate_cached(&self) {
+        self.duplicates_cached.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_duplicate_db(&self) {
+        self.duplicates_db.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_delivered(&self) {
+        self.delivered.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> WsMetricsSnapshot {
+        WsMetricsSnapshot {
+            messages_received: self.messages_received.load(Ordering::Relaxed),
+            reconnects: self.reconnects.load(Ordering::Relaxed),
+            parse_errors: self.parse_errors.load(Ordering::Relaxed),
+            oversized_frames: self.oversized_frames.load(Ordering::Relaxed),
+            events: self.events.lock().clone(),
+            duplicates_cached: self.duplicates_cached.load(Ordering::Relaxed),
+            duplicates_db: self.duplicates_db.load(Ordering::Relaxed),
+            delivered: self.delivered.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preview_never_splits_a_multibyte_char() {
+        let text = format!("{}é and more", "a".repeat(99));
+        assert_eq!(payload_preview(&text, 100), "a".repeat(99));
+        assert_eq!(payload_preview(&text, 101), format!("{}é", "a".repeat(99)));
+    }
+
+    #[test]
+    fn short_payloads_are_returned_whole() {
+        assert_eq!(payload_preview("{}", 100), "{}");
+        assert_eq!(payload_preview("", 100), "");
+    }
+}
@@ -0,0 +1,104 @@
+use crate::repository::{GuildBranding, GuildSettingsRepository};
+use parking_lot::Mutex;
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, GuildId};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+static BRANDING_CACHE: OnceLock<Mutex<HashMap<u64, GuildBranding>>> = OnceLock::new();
+
+fn cache() -> &'static Mutex<HashMap<u64, GuildBranding>> {
+    BRANDING_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Branding for `guild_id`, served from memory after the first lookup.
+///
+/// Guilds without settings, and failed lookups, get the default branding.
+pub async fn for_guild(pool: &PgPool, guild_id: u64) -> GuildBranding {
+    if let Some(branding) = cache().lock().get(&guild_id) {
+        return branding.clone();
+    }
+
+    match GuildSettingsRepository::get_branding(pool, guild_id).await {
+        Ok(branding) => {
+            let branding = branding.unwrap_or_default();
+            cache().lock().insert(guild_id, branding.clone());
+            branding
+        }
+        Err(e) => {
+            eprintln!(
+                "[WARN] Failed to load branding for guild {}: {}",
+                guild_id, e
+            );
+            GuildBranding::default()
+        }
+    }
+}
+
+/// Stores the branding and drops the cached copy so the next lookup reloads it.
+pub async fn set(
+    pool: &PgPool,
+    guild_id: u64,
+    branding: &GuildBranding,
+) -> Result<(), sqlx::Error> {
+    GuildSettingsRepository::set_branding(pool, guild_id, branding).await?;
+    cache().lock().remove(&guild_id);
+    Ok(())
+}
+
+/// Drops every cached guild so branding edited in the database is re-read.
+/// Returns how many guilds were cached.
+pub fn clear_cache() -> usize {
+    let mut cache = cache().lock();
+    let cleared = cache.len();
+    cache.clear();
+    cleared
+}
+
+/// Applies the guild's footer, accent colour and thumbnail over `embed`.
+///
+/// Only the fields the guild has set are replaced, so call this after the
+/// embed's own defaults and before any per-message thumbnail (like track art).
+/// Embeds sent outside a guild are returned unchanged.
+pub async fn branded_embed(
+    pool: &PgPool,
+    guild_id: Option<GuildId>,
+    mut embed: CreateEmbed,
+) -> CreateEmbed {
+    let Some(guild_id) = guild_id else {
+        return embed;
+    };
+    let branding = for_guild(pool, guild_id.get()).await;
+
+    if let Some(footer) = &branding.brand_footer {
+        embed = embed.footer(CreateEmbedFooter::new(footer));
+    }
+    if let Some(color) = branding.brand_color {
+        embed = embed.color(color as u32);
+    }
+    if let Some(url) = &branding.brand_thumbnail_url {
+        embed = embed.thumbnail(url);
+    }
+    embed
+}
+
+/// Parses `#1DB954`, `0x1DB954` or `1DB954` into an RGB value.
+pub fn parse_color(input: &str) -> Option<u32> {
+    let hex = input.trim();
+    let hex = hex
+        .strip_prefix('#')
+        .or_else(|| hex.strip_prefix("0x"))
+        .or_else(|| hex.strip_prefix("0X"))
+        .unwrap_or(hex);
+
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok()
+}
+
+/// Whether `url` is an absolute http(s) URL Discord can load as a thumbnail.
+pub fn is_valid_thumbnail_url(url: &str) -> bool {
+    reqwest::Url::parse(url.trim())
+        .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.host().is_some())
+}
@@ -216,3 +216,20 @@ pub fn voice_leave(
 
     embed
 }
+
+/// Audit log entry for a moderation action
+pub fn mod_action(
+    action: &str,
+    target: &str,
+    moderator: &str,
+    reason: &str,
+    color: u32,
+) -> CreateEmbed {
+    CreateEmbed::new()
+        .title(format!("[MOD] {}", action))
+        .field("Target", target, true)
+        .field("Moderator", moderator, true)
+        .field("Reason", reason, false)
+        .color(color)
+        .timestamp(serenity::all::Timestamp::now())
+}
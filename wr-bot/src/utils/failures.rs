@@ -0,0 +1,65 @@
+use parking_lot::Mutex;
+use poise::serenity_prelude as serenity;
+use std::collections::HashMap;
+
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+
+/// Whether `error` means the channel was deleted or the bot lost access to it.
+pub fn is_unreachable(error: &serenity::Error) -> bool {
+    match error {
+        serenity::Error::Http(e) => matches!(e.status_code().map(|s| s.as_u16()), Some(403 | 404)),
+        _ => false,
+    }
+}
+
+/// Counts consecutive "unreachable" send failures per feed and channel, so
+/// dead subscriptions can be disabled instead of retried forever.
+///
+/// A zero threshold disables tracking.
+pub struct FailureTracker {
+    threshold: u32,
+    counts: Mutex<HashMap<(&'static str, u64), u32>>,
+}
+
+impl FailureTracker {
+    pub fn new(threshold: u32) -> Self {
+        Self {
+            threshold,
+            counts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn record_success(&self, feed: &'static str, channel_id: u64) {
+        self.counts.lock().remove(&(feed, channel_id));
+    }
+
+    /// Records a failed send and returns true once the channel has hit the
+    /// threshold, resetting its count. Transient errors are ignored.
+    pub fn record_failure(
+        &self,
+        feed: &'static str,
+        channel_id: u64,
+        error: &serenity::Error,
+    ) -> bool {
+        if self.threshold == 0 || !is_unreachable(error) {
+            return false;
+        }
+
+        let mut counts = self.counts.lock();
+        let count = counts.entry((feed, channel_id)).or_insert(0);
+        *count += 1;
+
+        if *count >= self.threshold {
+            counts.remove(&(feed, channel_id));
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl Default for FailureTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_FAILURE_THRESHOLD)
+    }
+}
@@ -0,0 +1,110 @@
+use crate::repository::{GuildFeatures, GuildFeaturesRepository};
+use parking_lot::Mutex;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Command category that can be switched off per guild.
+///
+/// Commands opt in through `category = "..."` in their `#[poise::command]`
+/// attribute; untagged commands are always available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum FeatureCategory {
+    Forex,
+    Stock,
+    Calendar,
+    Music,
+    #[name = "AI"]
+    Ai,
+    Moderation,
+}
+
+impl FeatureCategory {
+    pub fn column(self) -> &'static str {
+        match self {
+            FeatureCategory::Forex => "forex",
+            FeatureCategory::Stock => "stock",
+            FeatureCategory::Calendar => "calendar",
+            FeatureCategory::Music => "music",
+            FeatureCategory::Ai => "ai",
+            FeatureCategory::Moderation => "moderation",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FeatureCategory::Forex => "Forex",
+            FeatureCategory::Stock => "Stock",
+            FeatureCategory::Calendar => "Calendar",
+            FeatureCategory::Music => "Music",
+            FeatureCategory::Ai => "AI",
+            FeatureCategory::Moderation => "Moderation",
+        }
+    }
+
+    /// Maps a poise command category to a feature, ignoring case.
+    pub fn from_category(category: &str) -> Option<Self> {
+        match category.trim().to_lowercase().as_str() {
+            "forex" => Some(FeatureCategory::Forex),
+            "stock" => Some(FeatureCategory::Stock),
+            "calendar" => Some(FeatureCategory::Calendar),
+            "music" => Some(FeatureCategory::Music),
+            "ai" => Some(FeatureCategory::Ai),
+            "moderation" => Some(FeatureCategory::Moderation),
+            _ => None,
+        }
+    }
+}
+
+static FEATURE_CACHE: OnceLock<Mutex<HashMap<u64, GuildFeatures>>> = OnceLock::new();
+
+fn cache() -> &'static Mutex<HashMap<u64, GuildFeatures>> {
+    FEATURE_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Feature flags for `guild_id`, served from memory after the first lookup.
+///
+/// Guilds without a row have everything enabled. A failed lookup is not
+/// cached and also falls back to everything enabled, so a database hiccup
+/// never locks commands.
+pub async fn for_guild(pool: &PgPool, guild_id: u64) -> GuildFeatures {
+    if let Some(features) = cache().lock().get(&guild_id) {
+        return *features;
+    }
+
+    match GuildFeaturesRepository::get(pool, guild_id).await {
+        Ok(features) => {
+            let features = features.unwrap_or_default();
+            cache().lock().insert(guild_id, features);
+            features
+        }
+        Err(e) => {
+            eprintln!(
+                "[WARN] Failed to load feature flags for guild {}: {}",
+                guild_id, e
+            );
+            GuildFeatures::default()
+        }
+    }
+}
+
+/// Stores a flag and drops the cached copy so the next lookup reloads it.
+pub async fn set(
+    pool: &PgPool,
+    guild_id: u64,
+    category: FeatureCategory,
+    enabled: bool,
+) -> Result<(), sqlx::Error> {
+    GuildFeaturesRepository::set(pool, guild_id, category, enabled).await?;
+    cache().lock().remove(&guild_id);
+    Ok(())
+}
+
+/// Drops every cached guild so flags edited in the database are re-read.
+/// Returns how many guilds were cached.
+pub fn clear_cache() -> usize {
+    let mut cache = cache().lock();
+    let cleared = cache.len();
+    cache.clear();
+    cleared
+}
@@ -0,0 +1,83 @@
+use crate::repository::GuildSettingsRepository;
+use sqlx::PgPool;
+
+/// Number formatting convention for a guild.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum Locale {
+    /// `1,234.56`
+    #[default]
+    #[name = "English (1,234.56)"]
+    En,
+    /// `1.234,56`
+    #[name = "Indonesia (1.234,56)"]
+    Id,
+}
+
+impl Locale {
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Id => "id",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_lowercase().as_str() {
+            "en" => Some(Locale::En),
+            "id" => Some(Locale::Id),
+            _ => None,
+        }
+    }
+
+    /// Locale configured for `guild_id`, falling back to English when unset,
+    /// unknown or the lookup fails.
+    pub async fn for_guild(pool: &PgPool, guild_id: Option<u64>) -> Self {
+        let Some(guild_id) = guild_id else {
+            return Self::default();
+        };
+        GuildSettingsRepository::get_locale(pool, guild_id)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|code| Self::from_code(&code))
+            .unwrap_or_default()
+    }
+
+    fn separators(self) -> (char, char) {
+        match self {
+            Locale::En => (',', '.'),
+            Locale::Id => ('.', ','),
+        }
+    }
+
+    /// Formats `value` with `decimals` decimal places and grouped thousands.
+    pub fn format_number(self, value: f64, decimals: usize) -> String {
+        let (group, decimal) = self.separators();
+        let formatted = format!("{:.*}", decimals, value.abs());
+        let (int_part, frac_part) = match formatted.split_once('.') {
+            Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+            None => (formatted.as_str(), None),
+        };
+
+        let mut out = String::new();
+        if value.is_sign_negative() && formatted.chars().any(|c| c.is_ascii_digit() && c != '0') {
+            out.push('-');
+        }
+        for (i, c) in int_part.chars().enumerate() {
+            if i > 0 && (int_part.len() - i) % 3 == 0 {
+                out.push(group);
+            }
+            out.push(c);
+        }
+        if let Some(frac_part) = frac_part {
+            out.push(decimal);
+            out.push_str(frac_part);
+        }
+        out
+    }
+
+    /// Formats a whole count with grouped thousands.
+    pub fn format_count(self, value: i64) -> String {
+        self.format_number(value as f64, 0)
+    }
+}
@@ -1,2 +1,18 @@
+pub mod branding;
 pub mod embed;
+pub mod failures;
+pub mod features;
+pub mod locale;
+pub mod pagination;
+pub mod pairs;
+pub mod permissions;
+pub mod ratelimit;
+pub mod registration;
+pub mod seen;
+pub mod sessions;
+pub mod sql;
 pub mod sys;
+pub mod template;
+pub mod text;
+pub mod threads;
+pub mod webhook;
@@ -0,0 +1,93 @@
+use poise::serenity_prelude::{
+    ButtonStyle, ComponentInteractionCollector, CreateActionRow, CreateButton, CreateEmbed,
+    CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+use std::future::Future;
+use std::time::Duration;
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// How long the buttons stay active after the last press.
+const PAGINATION_TIMEOUT: Duration = Duration::from_secs(120);
+
+fn buttons(prefix: &str, page: usize, has_next: bool) -> Vec<CreateActionRow> {
+    vec![CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("{}_prev", prefix))
+            .label("<")
+            .style(ButtonStyle::Secondary)
+            .disabled(page == 0),
+        CreateButton::new(format!("{}_next", prefix))
+            .label(">")
+            .style(ButtonStyle::Secondary)
+            .disabled(!has_next),
+    ])]
+}
+
+/// Sends page 0 and lets the invoking user flip through pages with buttons.
+///
+/// `render` builds the embed for a zero-based page and reports whether a
+/// following page exists. Buttons are removed after [`PAGINATION_TIMEOUT`]
+/// without a press.
+pub async fn paginate<U, F, Fut>(
+    ctx: poise::Context<'_, U, Error>,
+    mut render: F,
+) -> Result<(), Error>
+where
+    U: Send + Sync + 'static,
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = Result<(CreateEmbed, bool), Error>>,
+{
+    let mut page = 0;
+    let (embed, has_next) = render(page).await?;
+
+    if !has_next {
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let prefix = format!("page_{}", ctx.id());
+    let reply = ctx
+        .send(
+            poise::CreateReply::default()
+                .embed(embed)
+                .components(buttons(&prefix, page, has_next)),
+        )
+        .await?;
+
+    loop {
+        let filter_prefix = prefix.clone();
+        let Some(interaction) =
+            ComponentInteractionCollector::new(ctx.serenity_context().shard.clone())
+                .author_id(ctx.author().id)
+                .channel_id(ctx.channel_id())
+                .filter(move |i| i.data.custom_id.starts_with(&filter_prefix))
+                .timeout(PAGINATION_TIMEOUT)
+                .await
+        else {
+            break;
+        };
+
+        if interaction.data.custom_id.ends_with("_next") {
+            page += 1;
+        } else {
+            page = page.saturating_sub(1);
+        }
+
+        let (embed, has_next) = render(page).await?;
+        interaction
+            .create_response(
+                ctx.http(),
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .embed(embed)
+                        .components(buttons(&prefix, page, has_next)),
+                ),
+            )
+            .await?;
+    }
+
+    let _ = reply
+        .edit(ctx, poise::CreateReply::default().components(vec![]))
+        .await;
+    Ok(())
+}
@@ -0,0 +1,99 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PairCategory {
+    Major,
+    Minor,
+    Metal,
+}
+
+impl PairCategory {
+    pub fn label(self) -> &'static str {
+        match self {
+            PairCategory::Major => "Major",
+            PairCategory::Minor => "Minor",
+            PairCategory::Metal => "Metal",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PairInfo {
+    pub symbol: String,
+    pub base: String,
+    pub quote: String,
+    pub category: PairCategory,
+    /// Decimal place of one pip (4 means 0.0001).
+    pub pip_decimals: u32,
+    /// Decimal places prices are usually quoted with.
+    pub price_decimals: u32,
+    /// False when the symbol is not in [`PAIRS`] and the values were guessed.
+    pub known: bool,
+}
+
+impl PairInfo {
+    pub fn pip_size(&self) -> f64 {
+        10f64.powi(-(self.pip_decimals as i32))
+    }
+}
+
+/// (symbol, category, pip decimals, price decimals)
+const PAIRS: &[(&str, PairCategory, u32, u32)] = &[
+    ("EURUSD", PairCategory::Major, 4, 5),
+    ("GBPUSD", PairCategory::Major, 4, 5),
+    ("USDJPY", PairCategory::Major, 2, 3),
+    ("USDCHF", PairCategory::Major, 4, 5),
+    ("AUDUSD", PairCategory::Major, 4, 5),
+    ("USDCAD", PairCategory::Major, 4, 5),
+    ("NZDUSD", PairCategory::Major, 4, 5),
+    ("EURGBP", PairCategory::Minor, 4, 5),
+    ("EURJPY", PairCategory::Minor, 2, 3),
+    ("EURCHF", PairCategory::Minor, 4, 5),
+    ("EURAUD", PairCategory::Minor, 4, 5),
+    ("EURCAD", PairCategory::Minor, 4, 5),
+    ("GBPJPY", PairCategory::Minor, 2, 3),
+    ("GBPCHF", PairCategory::Minor, 4, 5),
+    ("GBPAUD", PairCategory::Minor, 4, 5),
+    ("AUDJPY", PairCategory::Minor, 2, 3),
+    ("AUDNZD", PairCategory::Minor, 4, 5),
+    ("CADJPY", PairCategory::Minor, 2, 3),
+    ("CHFJPY", PairCategory::Minor, 2, 3),
+    ("NZDJPY", PairCategory::Minor, 2, 3),
+    ("XAUUSD", PairCategory::Metal, 1, 2),
+    ("XAGUSD", PairCategory::Metal, 2, 3),
+];
+
+/// Strips separators and uppercases, so `eur/usd` becomes `EURUSD`.
+pub fn normalize_symbol(symbol: &str) -> String {
+    symbol
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_uppercase()
+}
+
+/// Looks up pair metadata, inferring defaults for unknown six-letter symbols.
+pub fn pair_info(symbol: &str) -> Option<PairInfo> {
+    let symbol = normalize_symbol(symbol);
+    if symbol.len() != 6 {
+        return None;
+    }
+
+    let (base, quote) = symbol.split_at(3);
+
+    let (category, pip_decimals, price_decimals, known) =
+        match PAIRS.iter().find(|(s, ..)| *s == symbol) {
+            Some((_, category, pip, price)) => (*category, *pip, *price, true),
+            None if base.starts_with('X') => (PairCategory::Metal, 1, 2, false),
+            None if quote == "JPY" => (PairCategory::Minor, 2, 3, false),
+            None => (PairCategory::Minor, 4, 5, false),
+        };
+
+    Some(PairInfo {
+        base: base.to_string(),
+        quote: quote.to_string(),
+        symbol,
+        category,
+        pip_decimals,
+        price_decimals,
+        known,
+    })
+}
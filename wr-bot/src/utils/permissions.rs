@@ -0,0 +1,50 @@
+use crate::utils::embed;
+use poise::serenity_prelude::{ChannelId, ChannelType, CreateEmbed};
+
+/// Whether the bot can send embeds in `channel_id`: it must be a text or
+/// announcement channel where the bot has `SEND_MESSAGES` and `EMBED_LINKS`.
+pub async fn can_post<U: Send + Sync + 'static, E>(
+    ctx: poise::Context<'_, U, E>,
+    channel_id: ChannelId,
+) -> bool {
+    let channel = match ctx
+        .guild()
+        .and_then(|g| g.channels.get(&channel_id).cloned())
+    {
+        Some(channel) => channel,
+        None => match channel_id
+            .to_channel(ctx)
+            .await
+            .ok()
+            .and_then(|c| c.guild())
+        {
+            Some(channel) => channel,
+            None => return false,
+        },
+    };
+
+    if !matches!(channel.kind, ChannelType::Text | ChannelType::News) {
+        return false;
+    }
+
+    let Ok(member) = channel.guild_id.member(ctx, ctx.framework().bot_id).await else {
+        return false;
+    };
+    let Some(guild) = ctx.guild() else {
+        return false;
+    };
+
+    let permissions = guild.user_permissions_in(&channel, &member);
+    permissions.send_messages() && permissions.embed_links()
+}
+
+/// Reply for when [`can_post`] fails.
+pub fn cannot_post(channel_id: ChannelId) -> CreateEmbed {
+    embed::error(
+        "I can't post there",
+        &format!(
+            "<#{}> must be a text channel where I have **Send Messages** and **Embed Links**.",
+            channel_id
+        ),
+    )
+}
@@ -0,0 +1,76 @@
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token bucket rate limiter keyed by an id (usually a user id).
+///
+/// Each key may burst up to `capacity` requests and regains one token every
+/// `period / capacity`.
+pub struct RateLimiter {
+    capacity: f64,
+    refill_per_sec: f64,
+    buckets: Mutex<HashMap<u64, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(capacity: u32, period: Duration) -> Self {
+        let capacity = capacity.max(1) as f64;
+        Self {
+            capacity,
+            refill_per_sec: capacity / period.as_secs_f64().max(1.0),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Takes a token for `key`, or returns how long until one is available.
+    pub fn check(&self, key: u64) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock();
+        let bucket = buckets.entry(key).or_insert(Bucket {
+            tokens: self.capacity,
+            updated: now,
+        });
+
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let missing = 1.0 - bucket.tokens;
+            Err(Duration::from_secs_f64(missing / self.refill_per_sec))
+        }
+    }
+
+    /// Drops buckets that have fully refilled, since they behave like new ones.
+    pub fn prune(&self) {
+        let now = Instant::now();
+        let full_after = self.capacity / self.refill_per_sec;
+        self.buckets
+            .lock()
+            .retain(|_, b| now.duration_since(b.updated).as_secs_f64() < full_after);
+    }
+
+    /// Periodically calls [`prune`](Self::prune) on a background task.
+    pub fn spawn_pruner(self: &Arc<Self>, every: Duration) {
+        let limiter = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(every);
+            loop {
+                interval.tick().await;
+                match limiter.upgrade() {
+                    Some(limiter) => limiter.prune(),
+                    None => break,
+                }
+            }
+        });
+    }
+}
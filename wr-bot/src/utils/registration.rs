@@ -0,0 +1,106 @@
+use crate::repository::MetaRepository;
+use poise::serenity_prelude::{CreateCommand, GuildId, Http};
+use sqlx::PgPool;
+use std::fmt;
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// Where slash commands get registered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegistrationScope {
+    Global,
+    /// Single guild, used during development since it propagates instantly
+    Guild(GuildId),
+}
+
+impl RegistrationScope {
+    /// Reads `DEV_GUILD_ID`, falling back to global registration.
+    pub fn from_env() -> Self {
+        match std::env::var("DEV_GUILD_ID")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .filter(|id| *id != 0)
+        {
+            Some(id) => Self::Guild(GuildId::new(id)),
+            None => Self::Global,
+        }
+    }
+
+    fn hash_key(self) -> String {
+        match self {
+            Self::Global => "commands_hash_global".to_string(),
+            Self::Guild(guild_id) => format!("commands_hash_guild_{}", guild_id),
+        }
+    }
+}
+
+impl fmt::Display for RegistrationScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Global => write!(f, "globally"),
+            Self::Guild(guild_id) => write!(f, "in dev guild {}", guild_id),
+        }
+    }
+}
+
+/// FNV-1a, chosen because the result must stay stable across builds.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Hash of the application command payload that would be sent to Discord.
+pub fn command_set_hash(commands: &[CreateCommand]) -> Result<String, Error> {
+    // Going through `Value` sorts object keys, so map fields hash deterministically
+    let payload = serde_json::to_value(commands)?.to_string();
+    Ok(format!("{:016x}", fnv1a(payload.as_bytes())))
+}
+
+/// Whether registration was forced via `FORCE_REGISTER` or `--force-register`.
+pub fn force_requested() -> bool {
+    std::env::var("FORCE_REGISTER")
+        .map(|v| matches!(v.trim(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+        || std::env::args().any(|arg| arg == "--force-register")
+}
+
+/// Registers commands in `scope` only when they differ from the last set
+/// registered there.
+///
+/// Returns whether the register API was called.
+pub async fn register_if_changed<U, E>(
+    http: &Http,
+    pool: &PgPool,
+    commands: &[poise::Command<U, E>],
+    scope: RegistrationScope,
+    force: bool,
+) -> Result<bool, Error> {
+    let hash = command_set_hash(&poise::builtins::create_application_commands(commands))?;
+    let key = scope.hash_key();
+
+    let stored = match MetaRepository::get(pool, &key).await {
+        Ok(stored) => stored,
+        Err(e) => {
+            eprintln!("[WARN] Failed to read registered command hash: {}", e);
+            None
+        }
+    };
+
+    if !force && stored.as_deref() == Some(hash.as_str()) {
+        return Ok(false);
+    }
+
+    match scope {
+        RegistrationScope::Global => poise::builtins::register_globally(http, commands).await?,
+        RegistrationScope::Guild(guild_id) => {
+            poise::builtins::register_in_guild(http, commands, guild_id).await?
+        }
+    }
+
+    if let Err(e) = MetaRepository::set(pool, &key, &hash).await {
+        eprintln!("[WARN] Failed to store registered command hash: {}", e);
+    }
+
+    Ok(true)
+}
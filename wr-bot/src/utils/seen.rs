@@ -0,0 +1,54 @@
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Prune expired entries once the map grows past this many ids.
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// In-memory set of recently seen ids, each remembered for `ttl`.
+///
+/// A zero `ttl` disables the cache.
+pub struct SeenCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, Instant>>,
+}
+
+impl SeenCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether `id` was inserted within the last `ttl`.
+    pub fn contains(&self, id: &str) -> bool {
+        if self.ttl.is_zero() {
+            return false;
+        }
+        self.entries
+            .lock()
+            .get(id)
+            .is_some_and(|seen| seen.elapsed() < self.ttl)
+    }
+
+    pub fn insert(&self, id: String) {
+        if self.ttl.is_zero() {
+            return;
+        }
+
+        let mut entries = self.entries.lock();
+        if entries.len() >= PRUNE_THRESHOLD {
+            entries.retain(|_, seen| seen.elapsed() < self.ttl);
+        }
+        entries.insert(id, Instant::now());
+    }
+
+    /// Forgets every id, returning how many were remembered.
+    pub fn clear(&self) -> usize {
+        let mut entries = self.entries.lock();
+        let count = entries.len();
+        entries.clear();
+        count
+    }
+}
@@ -0,0 +1,107 @@
+use chrono::{DateTime, Datelike, NaiveTime, Timelike, Utc, Weekday};
+use chrono_tz::Tz;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Session {
+    Tokyo,
+    London,
+    NewYork,
+}
+
+impl Session {
+    pub fn label(self) -> &'static str {
+        match self {
+            Session::Tokyo => "Tokyo",
+            Session::London => "London",
+            Session::NewYork => "New York",
+        }
+    }
+
+    /// Local timezone and trading hours. Hours are in local time, so DST
+    /// shifts for London and New York are handled by the timezone.
+    fn hours(self) -> (Tz, u32, u32) {
+        match self {
+            Session::Tokyo => (chrono_tz::Asia::Tokyo, 9, 18),
+            Session::London => (chrono_tz::Europe::London, 8, 17),
+            Session::NewYork => (chrono_tz::America::New_York, 8, 17),
+        }
+    }
+
+    pub fn is_open(self, now: DateTime<Utc>) -> bool {
+        let (tz, open, close) = self.hours();
+        let local = now.with_timezone(&tz);
+        let weekday = !matches!(local.weekday(), Weekday::Sat | Weekday::Sun);
+        weekday && (open..close).contains(&local.hour())
+    }
+}
+
+const SESSIONS: [Session; 3] = [Session::Tokyo, Session::London, Session::NewYork];
+
+/// The FX market closes Friday 17:00 and reopens Sunday 17:00 New York time.
+pub fn is_weekend_close(now: DateTime<Utc>) -> bool {
+    let local = now.with_timezone(&chrono_tz::America::New_York);
+    let five_pm = NaiveTime::from_hms_opt(17, 0, 0).unwrap();
+
+    match local.weekday() {
+        Weekday::Fri => local.time() >= five_pm,
+        Weekday::Sat => true,
+        Weekday::Sun => local.time() < five_pm,
+        _ => false,
+    }
+}
+
+pub fn open_sessions(now: DateTime<Utc>) -> Vec<Session> {
+    SESSIONS.into_iter().filter(|s| s.is_open(now)).collect()
+}
+
+/// Human readable session line, e.g. `London + New York (overlap)`.
+pub fn describe(now: DateTime<Utc>) -> String {
+    if is_weekend_close(now) {
+        return "Market closed (weekend)".to_string();
+    }
+
+    let open = open_sessions(now);
+    match open.as_slice() {
+        [] => "Between major sessions".to_string(),
+        [only] => only.label().to_string(),
+        many => format!(
+            "{} (overlap)",
+            many.iter()
+                .map(|s| s.label())
+                .collect::<Vec<_>>()
+                .join(" + ")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(ts: &str) -> DateTime<Utc> {
+        ts.parse().unwrap()
+    }
+
+    #[test]
+    fn london_opens_an_hour_earlier_in_utc_during_bst() {
+        assert!(!Session::London.is_open(at("2026-01-13T07:30:00Z")));
+        assert!(Session::London.is_open(at("2026-07-14T07:30:00Z")));
+    }
+
+    #[test]
+    fn new_york_follows_the_us_dst_switch() {
+        // US clocks change on 2026-03-08, three weeks before the UK
+        assert!(!Session::NewYork.is_open(at("2026-03-06T12:30:00Z")));
+        assert!(Session::NewYork.is_open(at("2026-03-09T12:30:00Z")));
+        assert!(!Session::NewYork.is_open(at("2026-11-02T12:30:00Z")));
+        assert!(!Session::London.is_open(at("2026-03-09T07:30:00Z")));
+    }
+
+    #[test]
+    fn weekend_close_tracks_new_york_time() {
+        assert!(!is_weekend_close(at("2026-03-06T21:59:00Z")));
+        assert!(is_weekend_close(at("2026-03-06T22:00:00Z")));
+        assert!(is_weekend_close(at("2026-07-10T21:00:00Z")));
+        assert!(!is_weekend_close(at("2026-07-12T21:00:00Z")));
+    }
+}
@@ -0,0 +1,27 @@
+/// Builds an `ILIKE ... ESCAPE '\'` pattern matching `keyword` anywhere, with
+/// `%`, `_` and `\` in the keyword matched literally.
+pub fn contains_pattern(keyword: &str) -> String {
+    let mut pattern = String::with_capacity(keyword.len() + 2);
+    pattern.push('%');
+    for c in keyword.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards_in_keyword_are_escaped() {
+        assert_eq!(contains_pattern("100%"), "%100\\%%");
+        assert_eq!(contains_pattern("usd_jpy"), "%usd\\_jpy%");
+        assert_eq!(contains_pattern("a\\b"), "%a\\\\b%");
+        assert_eq!(contains_pattern("gold"), "%gold%");
+    }
+}
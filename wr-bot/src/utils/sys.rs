@@ -1,5 +1,30 @@
+use once_cell::sync::Lazy;
+use std::time::{Duration, Instant};
 use sysinfo::System;
 
+/// Process start time; forced in `main` so uptime counts from startup.
+pub static STARTED_AT: Lazy<Instant> = Lazy::new(Instant::now);
+
+pub fn uptime() -> Duration {
+    STARTED_AT.elapsed()
+}
+
+/// Formats a duration as e.g. `2d 3h 14m`.
+pub fn format_uptime(duration: Duration) -> String {
+    let total_minutes = duration.as_secs() / 60;
+    let days = total_minutes / (24 * 60);
+    let hours = (total_minutes / 60) % 24;
+    let minutes = total_minutes % 60;
+
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
 pub struct SysInfo {
     pub memory: String,
     pub cpu: String,
@@ -0,0 +1,128 @@
+/// Longest prompt template a guild may store.
+pub const MAX_TEMPLATE_LEN: usize = 1500;
+
+/// Phrases that try to override the surrounding instructions rather than
+/// describe a persona.
+const BLOCKED_PHRASES: &[&str] = &[
+    "ignore previous",
+    "ignore all",
+    "ignore the above",
+    "disregard",
+    "system prompt",
+    "you are now",
+];
+
+/// Replaces each `{name}` in `template` with its value. Unknown placeholders
+/// are left as they are.
+///
+/// Substitution is a single pass, so placeholders inside a value are never
+/// expanded.
+pub fn render(template: &str, values: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.find('}').and_then(|end| {
+            let name = &after[..end];
+            values
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, value)| (*value, end))
+        });
+
+        match value {
+            Some((value, end)) => {
+                out.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Checks a user-supplied template before it is stored.
+///
+/// Rejects templates that are empty or too long, use placeholders other than
+/// `allowed`, contain code fences or mass mentions that would break the reply,
+/// or include common prompt-injection phrases.
+pub fn validate(template: &str, allowed: &[&str]) -> Result<(), String> {
+    let trimmed = template.trim();
+    if trimmed.is_empty() {
+        return Err("Template is empty.".to_string());
+    }
+    if trimmed.chars().count() > MAX_TEMPLATE_LEN {
+        return Err(format!(
+            "Template is longer than {} characters.",
+            MAX_TEMPLATE_LEN
+        ));
+    }
+    if trimmed.contains("```") || trimmed.contains("@everyone") || trimmed.contains("@here") {
+        return Err("Template may not contain code fences or mass mentions.".to_string());
+    }
+
+    let lower = trimmed.to_lowercase();
+    if let Some(phrase) = BLOCKED_PHRASES.iter().find(|p| lower.contains(*p)) {
+        return Err(format!("Template may not contain \"{}\".", phrase));
+    }
+
+    let mut rest = trimmed;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            return Err("Template has an unclosed `{`.".to_string());
+        };
+        let name = &after[..end];
+        if !allowed.contains(&name) {
+            return Err(format!(
+                "Unknown placeholder `{{{}}}`. Allowed: {}",
+                name,
+                allowed
+                    .iter()
+                    .map(|a| format!("`{{{}}}`", a))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        rest = &after[end + 1..];
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_are_replaced() {
+        let text = render(
+            "Analyse {symbol} on {timeframe}.",
+            &[("symbol", "EURUSD"), ("timeframe", "H4")],
+        );
+        assert_eq!(text, "Analyse EURUSD on H4.");
+    }
+
+    #[test]
+    fn placeholders_inside_values_are_not_expanded() {
+        let text = render(
+            "{symbol} / {timeframe}",
+            &[("symbol", "{timeframe}"), ("timeframe", "H1")],
+        );
+        assert_eq!(text, "{timeframe} / H1");
+    }
+
+    #[test]
+    fn unknown_and_unclosed_braces_are_kept() {
+        assert_eq!(
+            render("{other} {symbol", &[("symbol", "X")]),
+            "{other} {symbol"
+        );
+    }
+}
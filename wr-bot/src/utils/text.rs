@@ -0,0 +1,140 @@
+/// Discord embed limits, in characters.
+pub const EMBED_TITLE_MAX: usize = 256;
+pub const EMBED_FIELD_NAME_MAX: usize = 256;
+pub const EMBED_FIELD_MAX: usize = 1024;
+pub const EMBED_DESCRIPTION_MAX: usize = 4096;
+pub const EMBED_FOOTER_MAX: usize = 2048;
+
+/// Shortens `text` to at most `max` characters, ending with `…`.
+///
+/// Cuts on the last whitespace when one is reasonably close to the limit so
+/// words are not split. Text already within the limit is returned unchanged.
+pub fn truncate_field(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+
+    // Leave room for the ellipsis
+    let cut = text
+        .char_indices()
+        .nth(max - 1)
+        .map(|(idx, _)| idx)
+        .unwrap_or(text.len());
+    let head = &text[..cut];
+
+    // Back off to a word boundary unless the cut already falls on one
+    let on_boundary = text[cut..].starts_with(char::is_whitespace);
+    let head = match head.rfind(char::is_whitespace) {
+        Some(space) if !on_boundary && space >= cut / 2 => &head[..space],
+        _ => head,
+    };
+
+    format!("{}…", head.trim_end())
+}
+
+/// [`truncate_field`] at the embed description limit.
+pub fn truncate_desc(text: &str) -> String {
+    truncate_field(text, EMBED_DESCRIPTION_MAX)
+}
+
+/// Splits `text` into pages of at most `max` characters, breaking between
+/// lines where possible and inside a line only when it alone is too long.
+pub fn split_pages(text: &str, max: usize) -> Vec<String> {
+    if max == 0 {
+        return Vec::new();
+    }
+
+    let mut pages = Vec::new();
+    let mut page = String::new();
+    let mut page_len = 0;
+
+    for line in text.lines() {
+        let mut line = line;
+        loop {
+            let line_len = line.chars().count();
+            let needed = if page.is_empty() {
+                line_len
+            } else {
+                line_len + 1
+            };
+
+            if page_len + needed <= max {
+                if !page.is_empty() {
+                    page.push('\n');
+                }
+                page.push_str(line);
+                page_len += needed;
+                break;
+            }
+
+            if !page.is_empty() {
+                pages.push(std::mem::take(&mut page));
+                page_len = 0;
+                continue;
+            }
+
+            // A single line longer than a page
+            let cut = line
+                .char_indices()
+                .nth(max)
+                .map_or(line.len(), |(idx, _)| idx);
+            pages.push(line[..cut].to_string());
+            line = &line[cut..];
+            if line.is_empty() {
+                break;
+            }
+        }
+    }
+
+    if !page.is_empty() {
+        pages.push(page);
+    }
+    pages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_at_the_limit_is_unchanged() {
+        assert_eq!(truncate_field("abcde", 5), "abcde");
+        assert_eq!(truncate_field("", 5), "");
+    }
+
+    #[test]
+    fn text_over_the_limit_ends_in_ellipsis_at_max_chars() {
+        let out = truncate_field("abcdef", 5);
+        assert_eq!(out, "abcd…");
+        assert_eq!(out.chars().count(), 5);
+    }
+
+    #[test]
+    fn truncation_backs_off_to_a_word_boundary() {
+        assert_eq!(truncate_field("hello world foo", 13), "hello world…");
+        // Too far back to be worth it, so the word is cut
+        assert_eq!(truncate_field("a verylongword", 10), "a verylon…");
+    }
+
+    #[test]
+    fn truncation_counts_chars_not_bytes() {
+        let out = truncate_field("ééééé", 3);
+        assert_eq!(out, "éé…");
+        assert_eq!(truncate_field("📈📉📈", 3), "📈📉📈");
+        assert_eq!(truncate_field("📈📉📈📉", 3), "📈📉…");
+    }
+
+    #[test]
+    fn pages_break_between_lines() {
+        assert_eq!(split_pages("one\ntwo\nthree", 7), ["one\ntwo", "three"]);
+    }
+
+    #[test]
+    fn long_lines_are_split_on_char_boundaries() {
+        assert_eq!(split_pages("ééééé", 2), ["éé", "éé", "é"]);
+        assert!(split_pages("text", 0).is_empty());
+    }
+}
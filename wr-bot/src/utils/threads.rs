@@ -0,0 +1,51 @@
+use poise::serenity_prelude::{AutoArchiveDuration, ChannelId, ChannelType, CreateThread, Http};
+
+/// Thread resolved by [`news_thread`].
+pub struct NewsThread {
+    pub id: ChannelId,
+    /// True when the thread was created just now and the caller should
+    /// store its id.
+    pub created: bool,
+}
+
+/// Returns the news thread under `parent`, reusing `stored` when it still
+/// exists and is not archived, otherwise creating a new public thread.
+///
+/// Returns `None` when the thread cannot be created (usually a missing
+/// Create Public Threads permission); callers then post in `parent`.
+pub async fn news_thread(
+    http: &Http,
+    parent: ChannelId,
+    stored: Option<i64>,
+    name: &str,
+) -> Option<NewsThread> {
+    if let Some(id) = stored
+        && let Ok(channel) = http.get_channel(ChannelId::new(id as u64)).await
+        && let Some(thread) = channel.guild()
+        && thread.parent_id == Some(parent)
+        && !thread.thread_metadata.is_some_and(|m| m.archived)
+    {
+        return Some(NewsThread {
+            id: thread.id,
+            created: false,
+        });
+    }
+
+    let builder = CreateThread::new(name)
+        .kind(ChannelType::PublicThread)
+        .auto_archive_duration(AutoArchiveDuration::OneWeek);
+
+    match parent.create_thread(http, builder).await {
+        Ok(thread) => Some(NewsThread {
+            id: thread.id,
+            created: true,
+        }),
+        Err(e) => {
+            println!(
+                "[WARN] Cannot create news thread in {}, posting to channel: {}",
+                parent, e
+            );
+            None
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use poise::serenity_prelude::{
+    self as serenity, ChannelId, CreateEmbed, CreateMessage, ExecuteWebhook, Http, WebhookId,
+};
+
+/// How many channels a news broadcast sends to at once, so one slow or
+/// rate-limited channel does not hold up the rest.
+pub const FANOUT_CONCURRENCY: usize = 5;
+
+/// How a news embed ended up being delivered by [`send_embed`].
+pub enum Delivery {
+    Channel,
+    Webhook,
+    /// The stored webhook no longer exists; the embed was sent to the
+    /// channel instead and the caller should clear the stored URL.
+    WebhookGone,
+}
+
+/// Extracts the id and token from a Discord webhook URL.
+pub fn parse_webhook_url(url: &str) -> Option<(WebhookId, String)> {
+    let url = reqwest::Url::parse(url.trim()).ok()?;
+    serenity::parse_webhook(&url).map(|(id, token)| (id, token.to_string()))
+}
+
+/// Sends `embed` through `webhook_url` when set, otherwise as a regular bot
+/// message in `channel_id`.
+pub async fn send_embed(
+    http: &Http,
+    channel_id: ChannelId,
+    webhook_url: Option<&str>,
+    content: Option<&str>,
+    embed: CreateEmbed,
+) -> Result<Delivery, serenity::Error> {
+    send_embed_to(http, channel_id, None, webhook_url, content, embed).await
+}
+
+/// Like [`send_embed`], but posts into `thread` (a thread under
+/// `channel_id`) when one is given.
+pub async fn send_embed_to(
+    http: &Http,
+    channel_id: ChannelId,
+    thread: Option<ChannelId>,
+    webhook_url: Option<&str>,
+    content: Option<&str>,
+    embed: CreateEmbed,
+) -> Result<Delivery, serenity::Error> {
+    let mut webhook_gone = false;
+
+    if let Some(url) = webhook_url {
+        match parse_webhook_url(url) {
+            Some((webhook_id, token)) => {
+                let mut payload = ExecuteWebhook::new().embed(embed.clone());
+                if let Some(content) = content {
+                    payload = payload.content(content);
+                }
+
+                match http
+                    .execute_webhook(webhook_id, thread, &token, false, vec![], &payload)
+                    .await
+                {
+                    Ok(_) => return Ok(Delivery::Webhook),
+                    Err(serenity::Error::Http(e))
+                        if e.status_code().map(|s| s.as_u16()) == Some(404) =>
+                    {
+                        webhook_gone = true;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            None => webhook_gone = true,
+        }
+    }
+
+    let mut message = CreateMessage::new().embed(embed);
+    if let Some(content) = content {
+        message = message.content(content);
+    }
+    thread
+        .unwrap_or(channel_id)
+        .send_message(http, message)
+        .await?;
+
+    Ok(if webhook_gone {
+        Delivery::WebhookGone
+    } else {
+        Delivery::Channel
+    })
+}